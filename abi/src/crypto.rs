@@ -17,6 +17,23 @@ pub fn verify_commitment(dice_bytes: &[u8], salt: &[u8; 32], commitment: &[u8; 3
     computed == *commitment
 }
 
+/// Commit to a round seed: commitment = SHA-256(seed)
+/// Used both for a player's dice seed and for the game chain's house seed
+pub fn commit_seed(seed: &[u8; 32]) -> [u8; 32] {
+    create_commitment(&[], seed)
+}
+
+/// Verify that a revealed seed matches its commitment
+pub fn verify_seed(seed: &[u8; 32], commitment: &[u8; 32]) -> bool {
+    commit_seed(seed) == *commitment
+}
+
+/// A player's entropy share for the round seed, derived from their private dice seed
+/// share = SHA-256("entropy" || seed); disclosing it does not disclose the seed
+pub fn entropy_share(seed: &[u8; 32]) -> [u8; 32] {
+    create_commitment(b"entropy", seed)
+}
+
 /// Generate a deterministic salt from chain data
 /// salt = SHA-256(block_hash || timestamp || player_chain_id || round_number)
 pub fn generate_salt(
@@ -33,6 +50,16 @@ pub fn generate_salt(
     hasher.finalize().into()
 }
 
+/// Hash a room password: hex(SHA-256(salt || password)), with a random salt drawn for each room
+/// Only the hash is sent to the lobby, but the plaintext is an argument of the user chain's operation,
+/// so it is recorded in that chain's block
+pub fn hash_room_password(salt: &str, password: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update(password.as_bytes());
+    hash_to_hex(&hasher.finalize().into())
+}
+
 /// Convert a 32-byte hash to hex string for display
 pub fn hash_to_hex(hash: &[u8; 32]) -> String {
     hash.iter().map(|b| format!("{:02x}", b)).collect()
@@ -75,6 +102,15 @@ mod tests {
         assert!(!verify_commitment(&dice_bytes, &wrong_salt, &commitment));
     }
 
+    #[test]
+    fn test_seed_commitment() {
+        let seed = [7u8; 32];
+        let commitment = commit_seed(&seed);
+
+        assert!(verify_seed(&seed, &commitment));
+        assert!(!verify_seed(&[8u8; 32], &commitment));
+    }
+
     #[test]
     fn test_hex_conversion() {
         let hash = [0xab, 0xcd, 0xef, 0x12, 0x34, 0x56, 0x78, 0x90,
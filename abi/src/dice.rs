@@ -4,15 +4,25 @@ use async_graphql::scalar;
 use async_graphql_derive::SimpleObject;
 use serde::{Deserialize, Serialize};
 
-/// The face value of a single die (1-6)
+/// The face value of a single die (1-6 on a standard die)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct DiceValue(u8);
 
 scalar!(DiceValue);
 
 impl DiceValue {
+    /// Faces on a standard die
+    pub const STANDARD_FACES: u8 = 6;
+    /// Largest die a game may be configured with
+    pub const MAX_FACES: u8 = 12;
+
     pub fn new(value: u8) -> Option<Self> {
-        if (1..=6).contains(&value) {
+        Self::with_faces(value, Self::STANDARD_FACES)
+    }
+
+    /// A face value on a die with `faces` sides
+    pub fn with_faces(value: u8, faces: u8) -> Option<Self> {
+        if faces <= Self::MAX_FACES && (1..=faces).contains(&value) {
             Some(DiceValue(value))
         } else {
             None
@@ -86,9 +96,13 @@ impl PlayerDice {
         self.dice.iter().map(|d| d.value()).collect()
     }
 
-    /// Create from byte representation
+    /// Create from byte representation (any face up to `DiceValue::MAX_FACES`;
+    /// the game's rules decide which faces are actually in play)
     pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
-        let dice: Option<Vec<DiceValue>> = bytes.iter().map(|&b| DiceValue::new(b)).collect();
+        let dice: Option<Vec<DiceValue>> = bytes
+            .iter()
+            .map(|&b| DiceValue::with_faces(b, DiceValue::MAX_FACES))
+            .collect();
         dice.map(PlayerDice::new)
     }
 }
@@ -96,8 +110,12 @@ impl PlayerDice {
 /// Dice commitment for commit-reveal scheme
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
 pub struct DiceCommitment {
-    /// SHA-256 hash of (dice_bytes || salt)
+    /// SHA-256 hash of the player's dice seed (the dice are rolled from it and the round seed)
     pub hash: [u8; 32],
+    /// SHA-256 hash of the player's entropy share for the round seed
+    pub entropy_hash: [u8; 32],
+    /// The entropy share, once disclosed
+    pub entropy: Option<[u8; 32]>,
     /// Has this commitment been revealed?
     pub revealed: bool,
     /// Was the player caught cheating (invalid reveal)?
@@ -105,9 +123,11 @@ pub struct DiceCommitment {
 }
 
 impl DiceCommitment {
-    pub fn new(hash: [u8; 32]) -> Self {
+    pub fn new(hash: [u8; 32], entropy_hash: [u8; 32]) -> Self {
         DiceCommitment {
             hash,
+            entropy_hash,
+            entropy: None,
             revealed: false,
             cheater: false,
         }
@@ -149,6 +169,9 @@ mod tests {
         assert!(DiceValue::new(1).is_some());
         assert!(DiceValue::new(6).is_some());
         assert!(DiceValue::new(7).is_none());
+        assert!(DiceValue::with_faces(8, 8).is_some());
+        assert!(DiceValue::with_faces(9, 8).is_none());
+        assert!(DiceValue::with_faces(13, 13).is_none());
     }
 
     #[test]
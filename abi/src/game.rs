@@ -1,12 +1,56 @@
 // Game state and bid types for Liar's Dice
 
-use crate::dice::{DiceCommitment, DiceValue, PlayerDice};
+use crate::crypto::verify_seed;
+use crate::dice::{DiceCommitment, DiceReveal, DiceValue, PlayerDice};
+use crate::player::{calculate_multiplayer_elo_changes, CHEAT_ELO_PENALTY};
+use crate::random::{combine_round_seed, roll_dice_from_seeds};
 use async_graphql::scalar;
 use async_graphql_derive::SimpleObject;
-use linera_sdk::linera_base_types::{AccountOwner, ChainId, Timestamp};
+use linera_sdk::linera_base_types::{AccountOwner, Amount, ChainId, Timestamp};
 use serde::{Deserialize, Serialize};
 
-pub type GameId = u64;
+scalar!(GameId);
+/// Globally unique game id: the lobby chain that matched the game plus that lobby's game counter
+/// Rematches keep the id of the matched game and count up `rematch`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct GameId {
+    pub lobby_chain: Option<ChainId>,
+    pub index: u64,
+    /// Rematches played since the matched game (0 for the matched game itself)
+    #[serde(default)]
+    pub rematch: u32,
+}
+
+impl GameId {
+    pub fn new(lobby_chain: ChainId, index: u64) -> Self {
+        GameId {
+            lobby_chain: Some(lobby_chain),
+            index,
+            rematch: 0,
+        }
+    }
+
+    /// Id of the rematch of this game
+    pub fn next_rematch(&self) -> GameId {
+        GameId {
+            rematch: self.rematch + 1,
+            ..*self
+        }
+    }
+}
+
+impl std::fmt::Display for GameId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.lobby_chain {
+            Some(lobby_chain) => write!(f, "{}:{}", lobby_chain, self.index)?,
+            None => write!(f, "{}", self.index)?,
+        }
+        if self.rematch > 0 {
+            write!(f, " (rematch {})", self.rematch)?;
+        }
+        Ok(())
+    }
+}
 
 /// A bid in Liar's Dice: "I bet there are at least N dice showing face X"
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
@@ -44,10 +88,237 @@ impl Bid {
         }
         false
     }
+}
+
+/// Strategy deciding which bids may open a round and which bids raise another
+pub trait BidOrdering {
+    /// Check if `bid` may open the bidding
+    fn is_valid_opening(&self, _bid: &Bid) -> bool {
+        true
+    }
+
+    /// Check if `bid` is a legal raise over `current`
+    fn is_raise(&self, bid: &Bid, current: &Bid) -> bool;
+}
+
+/// More dice, or the same number of dice on a higher face
+pub struct QuantityThenFaceOrdering;
+
+impl BidOrdering for QuantityThenFaceOrdering {
+    fn is_raise(&self, bid: &Bid, current: &Bid) -> bool {
+        bid.is_higher_than(current)
+    }
+}
+
+/// Every raise must bid more dice (any face)
+pub struct QuantityOnlyOrdering;
+
+impl BidOrdering for QuantityOnlyOrdering {
+    fn is_raise(&self, bid: &Bid, current: &Bid) -> bool {
+        bid.quantity > current.quantity
+    }
+}
+
+/// Perudo ordering with wild aces:
+/// - other faces raise as in `QuantityThenFaceOrdering`
+/// - switching to aces needs at least half the quantity, rounded up
+/// - raising aces needs more aces
+/// - switching back from aces needs double the quantity plus one
+/// - a round cannot be opened on aces
+pub struct AceConversionOrdering;
+
+impl BidOrdering for AceConversionOrdering {
+    fn is_valid_opening(&self, bid: &Bid) -> bool {
+        !bid.face.is_wild()
+    }
+
+    fn is_raise(&self, bid: &Bid, current: &Bid) -> bool {
+        let (quantity, current_quantity) = (bid.quantity as u16, current.quantity as u16);
+        match (current.face.is_wild(), bid.face.is_wild()) {
+            (false, false) => bid.is_higher_than(current),
+            (false, true) => quantity >= current_quantity.div_ceil(2),
+            (true, true) => quantity > current_quantity,
+            (true, false) => quantity > 2 * current_quantity,
+        }
+    }
+}
+
+/// Palifico round: the opening bid locks the face and every raise bids more of it
+pub struct PalificoOrdering;
+
+impl BidOrdering for PalificoOrdering {
+    fn is_raise(&self, bid: &Bid, current: &Bid) -> bool {
+        bid.face == current.face && bid.quantity > current.quantity
+    }
+}
+
+scalar!(BidRule);
+/// How a new bid must raise the current one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BidRule {
+    /// More dice, or the same number of dice on a higher face
+    #[default]
+    QuantityThenFace,
+    /// Every raise must bid more dice (any face)
+    QuantityOnly,
+    /// Perudo ace conversion (requires wild ones)
+    AceConversion,
+}
+
+impl BidRule {
+    /// The ordering strategy this rule selects
+    pub fn ordering(&self) -> &'static dyn BidOrdering {
+        match self {
+            BidRule::QuantityThenFace => &QuantityThenFaceOrdering,
+            BidRule::QuantityOnly => &QuantityOnlyOrdering,
+            BidRule::AceConversion => &AceConversionOrdering,
+        }
+    }
+
+    /// Check if `bid` is a legal raise over `current`
+    pub fn is_raise(&self, bid: &Bid, current: &Bid) -> bool {
+        self.ordering().is_raise(bid, current)
+    }
+}
+
+scalar!(RoundOpener);
+/// Who opens the bidding in the next round
+/// If that player is out, the next player to their left opens instead
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoundOpener {
+    /// The player who lost the die (the calza caller if they won one back instead)
+    #[default]
+    Loser,
+    /// The player who won the challenge
+    Winner,
+}
+
+scalar!(TimeoutPenalty);
+/// What happens to a player who misses the commit or bidding deadline
+/// (missing the reveal deadline always disqualifies: an unrevealed hand cannot be counted)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeoutPenalty {
+    /// Lose a die; a stalled bidder's round ends there
+    #[default]
+    LoseDie,
+    /// A stalled bidder calls "Liar!" on the current bid (loses a die instead if there is none to call)
+    AutoCallLiar,
+    /// Forfeit the game
+    Forfeit,
+}
+
+scalar!(SpectatorDelay);
+/// How far the spectator stream lags behind the game (the players' own stream is always live)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpectatorDelay {
+    /// Spectators see events as they happen
+    #[default]
+    Live,
+    /// An event is released once the game is this many rounds further
+    Rounds(u32),
+    /// An event is released this many seconds after it happened
+    Seconds(u64),
+}
+
+impl SpectatorDelay {
+    /// Can an event from `round`, emitted at `emitted_at`, be shown to spectators yet?
+    /// `current_round` is None once the game is over (everything is released then)
+    pub fn is_due(&self, round: u32, emitted_at: Timestamp, current_round: Option<u32>, now: Timestamp) -> bool {
+        match *self {
+            SpectatorDelay::Live => true,
+            SpectatorDelay::Rounds(rounds) => {
+                current_round.is_none_or(|current| current >= round.saturating_add(rounds))
+            }
+            SpectatorDelay::Seconds(seconds) => {
+                now.micros() >= emitted_at.micros().saturating_add(seconds.saturating_mul(1_000_000))
+            }
+        }
+    }
+}
+
+scalar!(GameRules);
+/// Rule set for a game, chosen when the match is made
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameRules {
+    /// Whether 1s count towards every other face
+    pub wild_ones: bool,
+    /// Dice each player starts with
+    pub starting_dice: u8,
+    /// Number of faces on each die
+    pub faces: u8,
+    /// How long players have to commit to their dice seed at the start of a round
+    pub commit_timeout_micros: u64,
+    /// How long the player on turn has to bid or challenge
+    pub bid_timeout_micros: u64,
+    /// How long players have to reveal after "Liar!" is called
+    pub reveal_timeout_micros: u64,
+    /// Penalty for missing the commit or bidding deadline
+    pub timeout_penalty: TimeoutPenalty,
+    /// How a bid must raise the previous one
+    pub bid_rule: BidRule,
+    /// Who opens the next round
+    pub opener: RoundOpener,
+    /// Whether a player dropping to one die triggers a palifico round
+    pub palifico: bool,
+    /// How far the spectator stream lags behind the game
+    #[serde(default)]
+    pub spectator_delay: SpectatorDelay,
+    /// Tokens each player locks in escrow on the game chain (zero for a friendly game)
+    #[serde(default)]
+    pub stake: Amount,
+    /// Share of the pot the game chain keeps, in basis points (10000 = 100%)
+    #[serde(default)]
+    pub rake_bps: u16,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        GameRules {
+            wild_ones: true,
+            starting_dice: PlayerDice::STARTING_DICE,
+            faces: DiceValue::STANDARD_FACES,
+            commit_timeout_micros: 60_000_000, // 60 seconds
+            bid_timeout_micros: 60_000_000,    // 60 seconds
+            reveal_timeout_micros: 60_000_000, // 60 seconds
+            timeout_penalty: TimeoutPenalty::LoseDie,
+            bid_rule: BidRule::QuantityThenFace,
+            opener: RoundOpener::Loser,
+            palifico: false,
+            spectator_delay: SpectatorDelay::Live,
+            stake: Amount::ZERO,
+            rake_bps: 0,
+        }
+    }
+}
+
+impl GameRules {
+    /// Most dice a player may start with (keeps the table total within a u8)
+    pub const MAX_STARTING_DICE: u8 = 10;
+
+    /// Check that the rules describe a playable game
+    pub fn validate(&self) -> Result<(), GameError> {
+        if self.starting_dice == 0 || self.starting_dice > Self::MAX_STARTING_DICE {
+            return Err(GameError::InvalidRules);
+        }
+        if self.faces < 2 || self.faces > DiceValue::MAX_FACES {
+            return Err(GameError::InvalidRules);
+        }
+        if self.commit_timeout_micros == 0 || self.bid_timeout_micros == 0 || self.reveal_timeout_micros == 0 {
+            return Err(GameError::InvalidRules);
+        }
+        // Ace conversion only makes sense when aces are wild
+        if self.bid_rule == BidRule::AceConversion && !self.wild_ones {
+            return Err(GameError::InvalidRules);
+        }
+        if self.rake_bps > StakeEscrow::MAX_RAKE_BPS {
+            return Err(GameError::InvalidRules);
+        }
+        Ok(())
+    }
 
-    /// Check if this is a valid first bid
-    pub fn is_valid_initial(&self) -> bool {
-        self.quantity >= 1 && self.face.value() >= 1 && self.face.value() <= 6
+    /// Check that a face exists on this game's dice
+    pub fn is_valid_face(&self, face: DiceValue) -> bool {
+        face.value() <= self.faces
     }
 }
 
@@ -60,6 +331,8 @@ pub enum GamePhase {
     WaitingForPlayers,
     /// Waiting for all players to commit their dice
     Committing,
+    /// Everyone has committed, waiting for every player's entropy share for the round seed
+    SharingEntropy,
     /// Active bidding phase
     Bidding,
     /// Someone called liar, waiting for reveals
@@ -70,6 +343,17 @@ pub enum GamePhase {
     GameOver,
 }
 
+scalar!(ChallengeKind);
+/// How the current bid was challenged
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChallengeKind {
+    /// "Liar!" - the bid is too high
+    #[default]
+    Liar,
+    /// "Calza" / spot on - the bid is exactly right
+    Exact,
+}
+
 scalar!(GameResult);
 /// The result of a game for a player
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -80,10 +364,121 @@ pub enum GameResult {
     Lost,
     /// Player was caught cheating (invalid reveal)
     Cheater,
-    /// Player timed out during reveal
+    /// Player was removed for missing a deadline
     TimedOut,
 }
 
+scalar!(GameError);
+/// Why the game engine rejected an action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameError {
+    /// No game is running on this chain
+    NoActiveGame,
+    /// The action is not allowed in the current phase
+    WrongPhase,
+    /// The table is already full
+    GameFull,
+    /// Not enough players to start
+    NotEnoughPlayers,
+    /// The player is not in this game
+    UnknownPlayer,
+    /// The player already has a seat at this table
+    AlreadyJoined,
+    /// The player has been eliminated and can no longer act
+    PlayerEliminated,
+    /// Someone else's turn
+    NotYourTurn,
+    /// A player may not call liar on their own bid
+    CannotCallOwnBid,
+    /// There is no bid to challenge yet
+    NoBidToChallenge,
+    /// The bid does not raise the current bid
+    BidNotHigher,
+    /// The opening bid is out of range
+    InvalidBid,
+    /// Every player has been eliminated
+    NoActivePlayers,
+    /// The player already committed this round
+    AlreadyCommitted,
+    /// Not every active player has committed yet
+    NotAllCommitted,
+    /// The player has no pending commitment to reveal
+    NothingToReveal,
+    /// The revealed seed does not match the commitment (player disqualified)
+    CommitmentMismatch,
+    /// Not every active player has revealed yet
+    NotAllRevealed,
+    /// The house seed does not match its published commitment
+    HouseSeedMismatch,
+    /// The rule set is not playable
+    InvalidRules,
+    /// In a palifico round every bid must keep the opening face
+    FaceLocked,
+    /// The current phase's deadline has not passed (or the phase has none)
+    DeadlineNotReached,
+    /// There is no open rematch offer for the game (never made, expired or already taken)
+    NoRematchOffer,
+    /// The entropy share does not match its commitment (player disqualified)
+    EntropyMismatch,
+    /// The player already shared their entropy this round
+    AlreadyShared,
+    /// The locked amount is not the game's stake
+    StakeMismatch,
+    /// The player's stake is already in escrow
+    AlreadyStaked,
+    /// A player is banned from matchmaking for cheating
+    PlayerBanned,
+    /// The game chain has no free slot (or is draining or under maintenance)
+    NoFreeGameChain,
+}
+
+impl std::fmt::Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            GameError::NoActiveGame => "no game in progress",
+            GameError::WrongPhase => "action not allowed in the current phase",
+            GameError::GameFull => "the table is full",
+            GameError::NotEnoughPlayers => "not enough players to start",
+            GameError::UnknownPlayer => "player is not in this game",
+            GameError::AlreadyJoined => "player already has a seat",
+            GameError::PlayerEliminated => "player has been eliminated",
+            GameError::NotYourTurn => "not this player's turn",
+            GameError::CannotCallOwnBid => "cannot challenge your own bid",
+            GameError::NoBidToChallenge => "no bid to challenge yet",
+            GameError::BidNotHigher => "bid must be higher than the current bid",
+            GameError::InvalidBid => "invalid opening bid",
+            GameError::NoActivePlayers => "no active players remaining",
+            GameError::AlreadyCommitted => "already committed this round",
+            GameError::NotAllCommitted => "waiting for other players to commit",
+            GameError::NothingToReveal => "no pending commitment to reveal",
+            GameError::CommitmentMismatch => "revealed seed does not match the commitment",
+            GameError::NotAllRevealed => "waiting for other players to reveal",
+            GameError::HouseSeedMismatch => "house seed does not match its commitment",
+            GameError::InvalidRules => "invalid game rules",
+            GameError::FaceLocked => "the face is locked this palifico round",
+            GameError::DeadlineNotReached => "the deadline has not passed yet",
+            GameError::NoRematchOffer => "no open rematch offer",
+            GameError::EntropyMismatch => "entropy share does not match the commitment",
+            GameError::AlreadyShared => "already shared entropy this round",
+            GameError::StakeMismatch => "locked amount does not match the stake",
+            GameError::AlreadyStaked => "stake already in escrow",
+            GameError::PlayerBanned => "a player is banned from matchmaking",
+            GameError::NoFreeGameChain => "the game chain cannot host another game",
+        };
+        f.write_str(msg)
+    }
+}
+
+/// An action the game chain refused, as reported back to the player who sent it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct RejectedAction {
+    pub game_id: Option<GameId>,
+    /// The rejected action (e.g. "MakeBid")
+    pub action: String,
+    pub error: GameError,
+    pub rejected_at: Option<Timestamp>,
+}
+
 /// Information about a player in a game
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
 pub struct GamePlayer {
@@ -96,6 +491,8 @@ pub struct GamePlayer {
     pub commitment: Option<DiceCommitment>,
     /// Revealed dice (only set after reveal phase)
     pub revealed_dice: Option<PlayerDice>,
+    /// Revealed dice seed (only set after reveal phase, used to re-derive the dice)
+    pub revealed_seed: Option<[u8; 32]>,
     /// Current dice count (public info - how many dice left)
     pub dice_count: u8,
     /// Is this player eliminated?
@@ -104,6 +501,10 @@ pub struct GamePlayer {
     pub is_turn: bool,
     /// Result of the game for this player
     pub result: GameResult,
+    /// Whether this player has already triggered their palifico round
+    pub had_palifico: bool,
+    /// Final placement (1 = winner), 0 while the player is still in the game
+    pub placement: u8,
 }
 
 impl GamePlayer {
@@ -115,10 +516,13 @@ impl GamePlayer {
             elo,
             commitment: None,
             revealed_dice: None,
+            revealed_seed: None,
             dice_count: PlayerDice::STARTING_DICE,
             eliminated: false,
             is_turn: false,
             result: GameResult::Pending,
+            had_palifico: false,
+            placement: 0,
         }
     }
 
@@ -135,6 +539,10 @@ impl GamePlayer {
     }
 
     pub fn lose_die(&mut self) {
+        // Already out (e.g. disqualified) - keep the recorded result
+        if self.eliminated {
+            return;
+        }
         if self.dice_count > 0 {
             self.dice_count -= 1;
         }
@@ -143,12 +551,340 @@ impl GamePlayer {
             self.result = GameResult::Lost;
         }
     }
+
+    /// Win a die back (a correct calza call), up to `max_dice`
+    pub fn gain_die(&mut self, max_dice: u8) {
+        if self.eliminated {
+            return;
+        }
+        if self.dice_count < max_dice {
+            self.dice_count += 1;
+        }
+    }
+
+    /// Disqualify this player for an invalid reveal
+    pub fn mark_cheater(&mut self) {
+        if let Some(ref mut c) = self.commitment {
+            c.mark_cheater();
+        }
+        self.result = GameResult::Cheater;
+        self.eliminated = true;
+        self.dice_count = 0;
+    }
+}
+
+/// What a timeout check did to the players who stalled the game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeoutOutcome {
+    /// Players who never committed were penalized; the commit window reopens if the game goes on
+    Commit { stalled: Vec<ChainId> },
+    /// The player on turn never acted and got `penalty` (`AutoCallLiar` only when there was a bid to call)
+    Bid { stalled: ChainId, penalty: TimeoutPenalty },
+    /// Players who never shared their entropy were disqualified; the round seed can now be fixed
+    Share { stalled: Vec<ChainId> },
+    /// Players who never revealed were disqualified; the round can now be resolved
+    Reveal { stalled: Vec<ChainId> },
+}
+
+/// A player's final standing in a finished game
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct PlayerStanding {
+    pub chain_id: Option<ChainId>,
+    pub name: String,
+    /// Final placement (1 = winner; players knocked out together share a placement)
+    pub placement: u8,
+    /// ELO going into the game
+    pub elo: u32,
+    /// ELO change from the game (pairwise against every other seat, less `CHEAT_ELO_PENALTY` for a cheater)
+    pub elo_change: i32,
+    /// Disqualified for cheating
+    #[serde(default)]
+    pub cheated: bool,
+}
+
+impl PlayerStanding {
+    /// ELO after applying this game's change (floored at 100)
+    pub fn new_elo(&self) -> u32 {
+        (self.elo as i32 + self.elo_change).max(100) as u32
+    }
+}
+
+/// Offer to play a finished game again with the same seats and rules
+/// Open for `WINDOW_MICROS` after the game ends; the rematch starts once every seat has agreed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct RematchOffer {
+    /// The finished game
+    pub game_id: GameId,
+    /// Game chain that hosted it (and hosts the rematch)
+    pub game_chain: Option<ChainId>,
+    pub rules: GameRules,
+    /// Seats for the rematch, in the original order with post-game ELO
+    pub players: Vec<GamePlayer>,
+    /// Players who asked for the rematch so far
+    pub accepted: Vec<ChainId>,
+    pub deadline: Option<Timestamp>,
+}
+
+impl RematchOffer {
+    /// How long players have to agree on a rematch
+    pub const WINDOW_MICROS: u64 = 60_000_000; // 60 seconds
+
+    /// Cheaters are not offered a seat
+    pub fn new(game: &LiarsDiceGame, game_chain: ChainId, timestamp: Timestamp) -> Self {
+        let standings = game.standings();
+        let players = game
+            .players
+            .iter()
+            .filter(|p| p.result != GameResult::Cheater)
+            .filter_map(|p| {
+                let (chain_id, owner) = (p.chain_id?, p.owner?);
+                let elo = standings
+                    .iter()
+                    .find(|s| s.chain_id == Some(chain_id))
+                    .map_or(p.elo, PlayerStanding::new_elo);
+                Some(GamePlayer::new(chain_id, owner, p.name.clone(), elo))
+            })
+            .collect();
+        RematchOffer {
+            game_id: game.game_id,
+            game_chain: Some(game_chain),
+            rules: game.rules.clone(),
+            players,
+            accepted: Vec::new(),
+            deadline: Some(LiarsDiceGame::deadline_after(timestamp, Self::WINDOW_MICROS)),
+        }
+    }
+
+    pub fn is_expired(&self, timestamp: Timestamp) -> bool {
+        self.deadline.is_some_and(|deadline| timestamp >= deadline)
+    }
+
+    /// Has every seat agreed? The rematch then waits for the lobby to approve it
+    pub fn is_agreed(&self) -> bool {
+        self.accepted.len() == self.players.len()
+    }
+
+    /// Record a player's agreement; returns true once every seat has agreed
+    pub fn accept(&mut self, chain_id: ChainId, timestamp: Timestamp) -> Result<bool, GameError> {
+        if self.is_expired(timestamp) {
+            return Err(GameError::NoRematchOffer);
+        }
+        if !self.players.iter().any(|p| p.chain_id == Some(chain_id)) {
+            return Err(GameError::UnknownPlayer);
+        }
+        if !self.accepted.contains(&chain_id) {
+            self.accepted.push(chain_id);
+        }
+        Ok(self.is_agreed())
+    }
+}
+
+/// A player's stake, locked from their bankroll balance and held by the game chain
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct StakeDeposit {
+    pub player_chain: Option<ChainId>,
+    pub amount: Amount,
+    pub locked_at: Option<Timestamp>,
+}
+
+/// Stakes of a game held in escrow until it ends
+/// The deposits sit in the game chain's bankroll pool; settling pays them out through bankroll debts
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct StakeEscrow {
+    pub game_id: GameId,
+    /// What each seat has to lock
+    pub stake: Amount,
+    pub rake_bps: u16,
+    pub deposits: Vec<StakeDeposit>,
+}
+
+impl StakeEscrow {
+    /// A rake of 100%
+    pub const MAX_RAKE_BPS: u16 = 10_000;
+
+    pub fn new(game_id: GameId, rules: &GameRules) -> Self {
+        StakeEscrow {
+            game_id,
+            stake: rules.stake,
+            rake_bps: rules.rake_bps,
+            deposits: Vec::new(),
+        }
+    }
+
+    pub fn has_deposited(&self, chain_id: ChainId) -> bool {
+        self.deposits.iter().any(|d| d.player_chain == Some(chain_id))
+    }
+
+    /// Record a player's locked stake
+    pub fn deposit(&mut self, chain_id: ChainId, amount: Amount, timestamp: Timestamp) -> Result<(), GameError> {
+        if amount != self.stake {
+            return Err(GameError::StakeMismatch);
+        }
+        if self.has_deposited(chain_id) {
+            return Err(GameError::AlreadyStaked);
+        }
+        self.deposits.push(StakeDeposit {
+            player_chain: Some(chain_id),
+            amount,
+            locked_at: Some(timestamp),
+        });
+        Ok(())
+    }
+
+    /// Everything locked so far
+    pub fn pot(&self) -> Amount {
+        self.deposits
+            .iter()
+            .fold(Amount::ZERO, |pot, deposit| pot.saturating_add(deposit.amount))
+    }
+
+    /// The game chain's cut of the pot
+    pub fn rake(&self) -> Amount {
+        let pot = self.pot().to_attos();
+        Amount::from_attos(pot.saturating_mul(self.rake_bps as u128) / Self::MAX_RAKE_BPS as u128)
+    }
+
+    /// Who gets what once the game is over
+    /// The winner takes the pot minus the rake; if there is no winner, or the winner never
+    /// locked their stake, the honest players get their deposits back and no rake is taken.
+    /// Cheaters never get their stake back: it is shared among the other depositors.
+    pub fn payouts(&self, winner: Option<ChainId>, cheaters: &[ChainId]) -> Vec<(ChainId, Amount)> {
+        if let Some(winner) = winner.filter(|winner| self.has_deposited(*winner)) {
+            return vec![(winner, self.pot().saturating_sub(self.rake()))];
+        }
+        let (forfeited, honest): (Vec<&StakeDeposit>, Vec<&StakeDeposit>) = self
+            .deposits
+            .iter()
+            .partition(|d| d.player_chain.is_some_and(|chain_id| cheaters.contains(&chain_id)));
+        let forfeited: u128 = forfeited.iter().map(|d| d.amount.to_attos()).sum();
+        let share = forfeited.checked_div(honest.len() as u128).unwrap_or(0);
+        // The first honest depositor also gets what does not split evenly
+        let remainder = forfeited - share * honest.len() as u128;
+        honest
+            .iter()
+            .enumerate()
+            .filter_map(|(i, d)| {
+                let extra = if i == 0 { share + remainder } else { share };
+                Some((d.player_chain?, d.amount.saturating_add(Amount::from_attos(extra))))
+            })
+            .collect()
+    }
+}
+
+/// A game in progress, as listed for players looking for something to watch
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct LiveGame {
+    pub game_id: GameId,
+    pub game_chain: Option<ChainId>,
+    pub phase: GamePhase,
+    pub round: u32,
+    /// Names of the seated players, in seat order
+    pub players: Vec<String>,
+    pub total_dice: u8,
+    /// Spectators watching the game chain (shared by every game it hosts, not counted per game)
+    pub chain_spectators: u32,
+}
+
+impl LiveGame {
+    pub fn new(game: &LiarsDiceGame, game_chain: ChainId, chain_spectators: u32) -> Self {
+        LiveGame {
+            game_id: game.game_id,
+            game_chain: Some(game_chain),
+            phase: game.phase.clone(),
+            round: game.round,
+            players: game.players.iter().map(|p| p.name.clone()).collect(),
+            total_dice: game.total_dice,
+            chain_spectators,
+        }
+    }
+}
+
+/// Dice a player showed at a showdown
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct RevealedHand {
+    pub player: Option<ChainId>,
+    pub dice: PlayerDice,
+}
+
+/// What a spectator has seen of one game: its latest state, the bids of the current round and
+/// the hands shown at the last showdown (kept until the next round's first bid)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct SpectatorView {
+    pub game_id: GameId,
+    pub game: Option<LiarsDiceGame>,
+    pub round: u32,
+    pub bids: Vec<Bid>,
+    pub reveals: Vec<RevealedHand>,
+    /// Player who lost a die in the last resolved round
+    pub last_loser: Option<ChainId>,
+    pub winner: Option<ChainId>,
+    pub finished: bool,
+}
+
+impl SpectatorView {
+    pub fn new(game_id: GameId) -> Self {
+        SpectatorView {
+            game_id,
+            ..Default::default()
+        }
+    }
+
+    pub fn update_game(&mut self, game: LiarsDiceGame) {
+        if game.round != self.round {
+            self.round = game.round;
+            self.bids.clear();
+        }
+        self.game = Some(game);
+    }
+
+    pub fn record_bid(&mut self, bid: Bid) {
+        // The first bid of a round closes the previous showdown
+        if self.bids.is_empty() {
+            self.reveals.clear();
+        }
+        self.bids.push(bid);
+    }
+
+    pub fn record_reveal(&mut self, player: ChainId, dice: PlayerDice) {
+        self.reveals.push(RevealedHand {
+            player: Some(player),
+            dice,
+        });
+    }
+
+    pub fn round_ended(&mut self, loser: Option<ChainId>) {
+        self.last_loser = loser;
+    }
+
+    pub fn game_ended(&mut self, winner: Option<ChainId>) {
+        self.winner = winner;
+        self.finished = true;
+    }
+}
+
+/// Outcome of a resolved round
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundOutcome {
+    /// How the bid was challenged
+    pub challenge: ChallengeKind,
+    /// Player who loses a die (none after a correct calza call)
+    pub loser: Option<ChainId>,
+    /// Player who wins a die back (a correct calza call)
+    pub gainer: Option<ChainId>,
+    /// How many dice actually matched the bid
+    pub actual_count: u8,
+    /// Whether the challenged bid was true (at least the bid for "Liar!", exactly the bid for calza)
+    pub bid_was_valid: bool,
+    /// The side of the challenge that was right
+    pub winner: ChainId,
 }
 
 /// The complete state of a Liar's Dice game
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
 pub struct LiarsDiceGame {
     pub game_id: GameId,
+    /// Rule set this game is played under
+    pub rules: GameRules,
     pub players: Vec<GamePlayer>,
     pub phase: GamePhase,
     /// Current round number (1-indexed)
@@ -159,12 +895,33 @@ pub struct LiarsDiceGame {
     pub bid_history: Vec<Bid>,
     /// The current (highest) bid
     pub current_bid: Option<Bid>,
-    /// Who called "Liar!" (if in reveal phase)
+    /// Who challenged the bid (if in reveal phase)
     pub liar_caller: Option<ChainId>,
+    /// Whether the challenge was "Liar!" or calza
+    pub challenge: ChallengeKind,
     /// Total dice in play (sum of all players' dice counts)
     pub total_dice: u8,
-    /// Reveal deadline timestamp (for timeout handling)
+    /// Deadline for every player to commit this round (Committing phase)
+    pub commit_deadline: Option<Timestamp>,
+    /// Deadline for the player on turn to bid or challenge (Bidding phase)
+    pub bid_deadline: Option<Timestamp>,
+    /// Reveal deadline timestamp (Revealing phase)
     pub reveal_deadline: Option<Timestamp>,
+    /// Game chain's commitment to its house seed for this round, published before players commit
+    pub house_seed_commitment: Option<[u8; 32]>,
+    /// Game chain's house seed for this round, disclosed once every player has shared their entropy
+    /// (chain state is readable by anyone syncing the chain, so it is no secret - the players' shares are)
+    pub house_seed: Option<[u8; 32]>,
+    /// Seed every hand of this round is rolled from: the house seed and every seat's entropy share
+    pub round_seed: Option<[u8; 32]>,
+    /// Player who lost a die in the last resolved round
+    pub last_round_loser: Option<ChainId>,
+    /// Player who won the challenge in the last resolved round
+    pub last_round_winner: Option<ChainId>,
+    /// Player whose drop to one die makes the current round palifico (no wilds, face locked)
+    pub palifico_player: Option<ChainId>,
+    /// Player whose drop to one die makes the next round palifico
+    pub next_palifico_player: Option<ChainId>,
     /// Winner of the game
     pub winner: Option<ChainId>,
     /// When the game started
@@ -176,11 +933,15 @@ pub struct LiarsDiceGame {
 impl LiarsDiceGame {
     pub const MAX_PLAYERS: usize = 6;
     pub const MIN_PLAYERS: usize = 2;
-    pub const REVEAL_TIMEOUT_MICROS: u64 = 60_000_000; // 60 seconds
 
     pub fn new(game_id: GameId) -> Self {
+        Self::with_rules(game_id, GameRules::default())
+    }
+
+    pub fn with_rules(game_id: GameId, rules: GameRules) -> Self {
         LiarsDiceGame {
             game_id,
+            rules,
             players: Vec::new(),
             phase: GamePhase::WaitingForPlayers,
             round: 0,
@@ -188,40 +949,62 @@ impl LiarsDiceGame {
             bid_history: Vec::new(),
             current_bid: None,
             liar_caller: None,
+            challenge: ChallengeKind::Liar,
             total_dice: 0,
+            commit_deadline: None,
+            bid_deadline: None,
             reveal_deadline: None,
+            house_seed_commitment: None,
+            house_seed: None,
+            round_seed: None,
+            last_round_loser: None,
+            last_round_winner: None,
+            palifico_player: None,
+            next_palifico_player: None,
             winner: None,
             started_at: None,
             ended_at: None,
         }
     }
 
-    pub fn add_player(&mut self, player: GamePlayer) -> bool {
+    pub fn add_player(&mut self, player: GamePlayer) -> Result<(), GameError> {
+        if self.phase != GamePhase::WaitingForPlayers {
+            return Err(GameError::WrongPhase);
+        }
         if self.players.len() >= Self::MAX_PLAYERS {
-            return false;
+            return Err(GameError::GameFull);
         }
-        if self.phase != GamePhase::WaitingForPlayers {
-            return false;
+        if player.chain_id.is_some() && self.players.iter().any(|p| p.chain_id == player.chain_id) {
+            return Err(GameError::AlreadyJoined);
         }
+        let player = GamePlayer {
+            dice_count: self.rules.starting_dice,
+            ..player
+        };
         self.total_dice += player.dice_count;
         self.players.push(player);
-        true
+        Ok(())
     }
 
     pub fn can_start(&self) -> bool {
         self.players.len() >= Self::MIN_PLAYERS && self.phase == GamePhase::WaitingForPlayers
     }
 
-    pub fn start_game(&mut self, timestamp: Timestamp) {
-        if self.can_start() {
-            self.phase = GamePhase::Committing;
-            self.round = 1;
-            self.started_at = Some(timestamp);
-            self.current_turn = 0;
-            if !self.players.is_empty() {
-                self.players[0].is_turn = true;
-            }
+    pub fn start_game(&mut self, timestamp: Timestamp) -> Result<(), GameError> {
+        if self.phase != GamePhase::WaitingForPlayers {
+            return Err(GameError::WrongPhase);
+        }
+        self.rules.validate()?;
+        if !self.can_start() {
+            return Err(GameError::NotEnoughPlayers);
         }
+        self.phase = GamePhase::Committing;
+        self.round = 1;
+        self.started_at = Some(timestamp);
+        self.commit_deadline = Some(Self::deadline_after(timestamp, self.rules.commit_timeout_micros));
+        self.current_turn = 0;
+        self.players[0].is_turn = true;
+        Ok(())
     }
 
     pub fn all_committed(&self) -> bool {
@@ -231,10 +1014,105 @@ impl LiarsDiceGame {
             .all(|p| p.commitment.is_some())
     }
 
-    pub fn start_bidding(&mut self) {
-        if self.all_committed() {
-            self.phase = GamePhase::Bidding;
+    /// Record a player's seed commitment for this round
+    pub fn commit_dice(&mut self, chain_id: &ChainId, commitment: DiceCommitment) -> Result<(), GameError> {
+        if self.phase != GamePhase::Committing {
+            return Err(GameError::WrongPhase);
+        }
+        let player = self.get_player_mut_by_chain(chain_id).ok_or(GameError::UnknownPlayer)?;
+        if player.eliminated {
+            return Err(GameError::PlayerEliminated);
+        }
+        if player.commitment.is_some() {
+            return Err(GameError::AlreadyCommitted);
+        }
+        player.set_commitment(commitment);
+        Ok(())
+    }
+
+    /// Publish the game chain's commitment to this round's house seed
+    pub fn set_house_commitment(&mut self, commitment: [u8; 32]) {
+        self.house_seed_commitment = Some(commitment);
+        self.house_seed = None;
+        self.round_seed = None;
+    }
+
+    /// Once every player has committed, ask for their entropy shares (within a fresh commit window)
+    pub fn start_sharing(&mut self, timestamp: Timestamp) -> Result<(), GameError> {
+        if self.phase != GamePhase::Committing {
+            return Err(GameError::WrongPhase);
+        }
+        if !self.all_committed() {
+            return Err(GameError::NotAllCommitted);
+        }
+        self.phase = GamePhase::SharingEntropy;
+        self.commit_deadline = Some(Self::deadline_after(timestamp, self.rules.commit_timeout_micros));
+        Ok(())
+    }
+
+    pub fn all_shared(&self) -> bool {
+        self.players
+            .iter()
+            .filter(|p| !p.eliminated)
+            .all(|p| p.commitment.as_ref().is_some_and(|c| c.entropy.is_some()))
+    }
+
+    /// Record a player's entropy share
+    /// A share that does not match the commitment disqualifies the player as a cheater
+    pub fn share_entropy(&mut self, chain_id: &ChainId, entropy: [u8; 32]) -> Result<(), GameError> {
+        if self.phase != GamePhase::SharingEntropy {
+            return Err(GameError::WrongPhase);
+        }
+        let player = self.get_player_mut_by_chain(chain_id).ok_or(GameError::UnknownPlayer)?;
+        if player.eliminated {
+            return Err(GameError::PlayerEliminated);
+        }
+        let commitment = player.commitment.as_mut().ok_or(GameError::NotAllCommitted)?;
+        if commitment.entropy.is_some() {
+            return Err(GameError::AlreadyShared);
+        }
+        if verify_seed(&entropy, &commitment.entropy_hash) {
+            commitment.entropy = Some(entropy);
+            Ok(())
+        } else {
+            player.mark_cheater();
+            self.settle_eliminations();
+            Err(GameError::EntropyMismatch)
+        }
+    }
+
+    /// Disclose the house seed, which must match the published commitment
+    pub fn reveal_house_seed(&mut self, seed: [u8; 32]) -> Result<(), GameError> {
+        match self.house_seed_commitment {
+            Some(ref commitment) if verify_seed(&seed, commitment) => {
+                self.house_seed = Some(seed);
+                Ok(())
+            }
+            _ => Err(GameError::HouseSeedMismatch),
+        }
+    }
+
+    /// Bidding starts once every player has shared their entropy and the house seed is public;
+    /// that fixes the round seed the players roll their hands from
+    pub fn start_bidding(&mut self, timestamp: Timestamp) -> Result<(), GameError> {
+        if self.phase != GamePhase::SharingEntropy {
+            return Err(GameError::WrongPhase);
         }
+        if !self.all_shared() {
+            return Err(GameError::NotAllCommitted);
+        }
+        let house_seed = self.house_seed.ok_or(GameError::HouseSeedMismatch)?;
+        let shares: Vec<[u8; 32]> = self
+            .players
+            .iter()
+            .filter(|p| !p.eliminated)
+            .filter_map(|p| p.commitment.as_ref()?.entropy)
+            .collect();
+        self.round_seed = Some(combine_round_seed(&house_seed, &shares));
+        self.phase = GamePhase::Bidding;
+        self.commit_deadline = None;
+        self.bid_deadline = Some(Self::deadline_after(timestamp, self.rules.bid_timeout_micros));
+        Ok(())
     }
 
     pub fn get_current_player(&self) -> Option<&GamePlayer> {
@@ -249,18 +1127,49 @@ impl LiarsDiceGame {
         self.players.iter_mut().find(|p| p.chain_id.as_ref() == Some(chain_id))
     }
 
-    pub fn make_bid(&mut self, bid: Bid) -> bool {
+    /// Check that a player may act now: they are in the game, still active, and it is their turn
+    fn ensure_turn(&self, chain_id: Option<ChainId>) -> Result<(), GameError> {
+        let chain_id = chain_id.ok_or(GameError::UnknownPlayer)?;
+        let seat = self
+            .players
+            .iter()
+            .position(|p| p.chain_id == Some(chain_id))
+            .ok_or(GameError::UnknownPlayer)?;
+        if self.players[seat].eliminated {
+            return Err(GameError::PlayerEliminated);
+        }
+        if seat != self.current_turn as usize {
+            return Err(GameError::NotYourTurn);
+        }
+        Ok(())
+    }
+
+    /// Place a bid for the player whose turn it is (`bid.bidder`)
+    /// The next player's deadline runs from `bid.timestamp`
+    pub fn make_bid(&mut self, bid: Bid) -> Result<(), GameError> {
         if self.phase != GamePhase::Bidding {
-            return false;
+            return Err(GameError::WrongPhase);
         }
+        self.ensure_turn(bid.bidder)?;
 
         // Check if bid is valid
+        if bid.quantity == 0 || !self.rules.is_valid_face(bid.face) {
+            return Err(GameError::InvalidBid);
+        }
+        let ordering: &dyn BidOrdering = if self.is_palifico() {
+            &PalificoOrdering
+        } else {
+            self.rules.bid_rule.ordering()
+        };
         if let Some(ref current) = self.current_bid {
-            if !bid.is_higher_than(current) {
-                return false;
+            if self.is_palifico() && bid.face != current.face {
+                return Err(GameError::FaceLocked);
             }
-        } else if !bid.is_valid_initial() {
-            return false;
+            if !ordering.is_raise(&bid, current) {
+                return Err(GameError::BidNotHigher);
+            }
+        } else if !ordering.is_valid_opening(&bid) {
+            return Err(GameError::InvalidBid);
         }
 
         // Update turn
@@ -270,34 +1179,46 @@ impl LiarsDiceGame {
         }
 
         // Record bid
+        if let Some(timestamp) = bid.timestamp {
+            self.bid_deadline = Some(Self::deadline_after(timestamp, self.rules.bid_timeout_micros));
+        }
         self.bid_history.push(bid.clone());
         self.current_bid = Some(bid);
 
         // Move to next non-eliminated player
-        // ✅ FIX: Handle Result from advance_turn
-        if self.advance_turn().is_err() {
-            // Failed to advance turn (all players eliminated)
-            return false;
-        }
+        self.advance_turn()
+    }
 
-        true
+    /// Challenge the current bid; only the player whose turn it is may call
+    pub fn call_liar(&mut self, caller: ChainId, timestamp: Timestamp) -> Result<(), GameError> {
+        self.challenge_bid(caller, ChallengeKind::Liar, timestamp)
+    }
+
+    /// Call calza: claim the current bid is exactly right
+    pub fn call_exact(&mut self, caller: ChainId, timestamp: Timestamp) -> Result<(), GameError> {
+        self.challenge_bid(caller, ChallengeKind::Exact, timestamp)
     }
 
-    pub fn call_liar(&mut self, caller: ChainId, timestamp: Timestamp) -> bool {
+    fn challenge_bid(&mut self, caller: ChainId, kind: ChallengeKind, timestamp: Timestamp) -> Result<(), GameError> {
         if self.phase != GamePhase::Bidding {
-            return false;
+            return Err(GameError::WrongPhase);
         }
-        if self.current_bid.is_none() {
-            return false; // Can't call liar on first turn
+        let bidder = match self.current_bid {
+            Some(ref bid) => bid.bidder,
+            None => return Err(GameError::NoBidToChallenge), // Can't call liar on first turn
+        };
+        if bidder == Some(caller) {
+            return Err(GameError::CannotCallOwnBid);
         }
+        self.ensure_turn(Some(caller))?;
 
         self.liar_caller = Some(caller);
+        self.challenge = kind;
         self.phase = GamePhase::Revealing;
-        self.reveal_deadline = Some(Timestamp::from(
-            timestamp.micros() + Self::REVEAL_TIMEOUT_MICROS,
-        ));
+        self.bid_deadline = None;
+        self.reveal_deadline = Some(Self::deadline_after(timestamp, self.rules.reveal_timeout_micros));
 
-        true
+        Ok(())
     }
 
     pub fn all_revealed(&self) -> bool {
@@ -313,8 +1234,56 @@ impl LiarsDiceGame {
             })
     }
 
+    /// Record a player's reveal (dice + seed)
+    /// A seed that does not match the commitment disqualifies the player as a cheater
+    /// (the reveal still counts towards `all_revealed`, so the round can resolve)
+    pub fn record_reveal(&mut self, chain_id: &ChainId, reveal: DiceReveal) -> Result<(), GameError> {
+        if self.phase != GamePhase::Revealing {
+            return Err(GameError::WrongPhase);
+        }
+        let player = self.get_player_mut_by_chain(chain_id).ok_or(GameError::UnknownPlayer)?;
+        if player.eliminated {
+            return Err(GameError::PlayerEliminated);
+        }
+        let seed_matches = match player.commitment.as_ref() {
+            Some(c) if !c.revealed => verify_seed(&reveal.salt, &c.hash),
+            _ => return Err(GameError::NothingToReveal),
+        };
+
+        if seed_matches {
+            if let Some(ref mut c) = player.commitment {
+                c.mark_revealed();
+            }
+            player.revealed_dice = Some(reveal.dice);
+            player.revealed_seed = Some(reveal.salt);
+            Ok(())
+        } else {
+            player.mark_cheater();
+            Err(GameError::CommitmentMismatch)
+        }
+    }
+
+    /// Check that a player's revealed dice are exactly the ones rolled from
+    /// their seed combined with the round seed
+    pub fn dice_match_seeds(&self, player: &GamePlayer) -> bool {
+        match (&player.revealed_dice, &player.revealed_seed, &self.round_seed) {
+            (Some(dice), Some(player_seed), Some(round_seed)) => {
+                let expected = roll_dice_from_seeds(player.dice_count, self.rules.faces, player_seed, round_seed);
+                dice.count == player.dice_count && dice.to_bytes() == expected
+            }
+            _ => false,
+        }
+    }
+
+    /// Is the current round palifico?
+    pub fn is_palifico(&self) -> bool {
+        self.palifico_player.is_some()
+    }
+
     /// Count total dice of a given face across all revealed dice
+    /// (wilds never count in a palifico round)
     pub fn count_total_dice(&self, face: DiceValue, wilds_count: bool) -> u8 {
+        let wilds_count = wilds_count && !self.is_palifico();
         self.players
             .iter()
             .filter(|p| !p.eliminated)
@@ -324,84 +1293,337 @@ impl LiarsDiceGame {
     }
 
     /// Resolve the round after all reveals
-    /// Returns the ChainId of the player who loses a die
-    pub fn resolve_round(&mut self) -> Option<ChainId> {
-        if !self.all_revealed() {
-            return None;
+    /// Every revealed hand is re-derived from the combined seeds first; a player whose
+    /// dice did not come from those seeds is disqualified as a cheater
+    pub fn resolve_round(&mut self) -> Result<RoundOutcome, GameError> {
+        if self.phase != GamePhase::Revealing {
+            return Err(GameError::WrongPhase);
         }
-
-        let bid = self.current_bid.as_ref()?;
-        let actual_count = self.count_total_dice(bid.face, true); // wilds count
-
-        // If actual count >= bid quantity, the caller was wrong (loses a die)
-        // If actual count < bid quantity, the bidder was wrong (loses a die)
-        let loser = if actual_count >= bid.quantity {
-            self.liar_caller.clone()
-        } else {
-            bid.bidder.clone()
-        };
-
-        // Apply penalty
-        if let Some(ref loser_chain) = loser {
-            if let Some(player) = self.get_player_mut_by_chain(loser_chain) {
-                player.lose_die();
-            }
+        if !self.all_revealed() {
+            return Err(GameError::NotAllRevealed);
         }
 
-        // Update total dice
-        self.total_dice = self.players.iter().map(|p| p.dice_count).sum();
+        let bid = self.current_bid.clone().ok_or(GameError::NoBidToChallenge)?;
 
-        // Check for winner
-        let active_players: Vec<_> = self
+        let forged: Vec<ChainId> = self
             .players
             .iter()
-            .filter(|p| !p.eliminated)
+            .filter(|p| !p.eliminated && !self.dice_match_seeds(p))
+            .filter_map(|p| p.chain_id)
             .collect();
+        for chain_id in &forged {
+            if let Some(player) = self.get_player_mut_by_chain(chain_id) {
+                player.mark_cheater();
+            }
+        }
+
+        let actual_count = self.count_total_dice(bid.face, self.rules.wild_ones);
+        let caller = self.liar_caller.ok_or(GameError::NoBidToChallenge)?;
+        let bidder = bid.bidder.ok_or(GameError::UnknownPlayer)?;
 
-        if active_players.len() == 1 {
-            let winner_chain = active_players[0].chain_id.clone();
-            self.winner = winner_chain.clone();
-            if let Some(ref wc) = winner_chain {
-                if let Some(player) = self.get_player_mut_by_chain(wc) {
-                    player.result = GameResult::Won;
+        let (bid_was_valid, loser, gainer, winner) = match self.challenge {
+            // If actual count >= bid quantity, the caller was wrong (loses a die)
+            // If actual count < bid quantity, the bidder was wrong (loses a die)
+            ChallengeKind::Liar => {
+                let bid_was_valid = actual_count >= bid.quantity;
+                if bid_was_valid {
+                    (true, Some(caller), None, bidder)
+                } else {
+                    (false, Some(bidder), None, caller)
                 }
             }
-            self.phase = GamePhase::GameOver;
-        } else {
+            // Calza: an exact count wins the caller a die back, anything else costs them one
+            ChallengeKind::Exact => {
+                if actual_count == bid.quantity {
+                    (true, None, Some(caller), caller)
+                } else {
+                    (false, Some(caller), None, bidder)
+                }
+            }
+        };
+        self.last_round_loser = loser;
+        self.last_round_winner = Some(winner);
+
+        // Apply penalty or reward
+        if let Some(ref loser) = loser {
+            self.take_die(loser);
+        }
+        if let Some(ref gainer) = gainer {
+            let max_dice = self.rules.starting_dice;
+            if let Some(player) = self.get_player_mut_by_chain(gainer) {
+                player.gain_die(max_dice);
+            }
+        }
+
+        self.reveal_deadline = None;
+        if !self.settle_eliminations() {
             self.phase = GamePhase::RoundEnd;
         }
 
-        loser
+        Ok(RoundOutcome {
+            challenge: self.challenge,
+            loser,
+            gainer,
+            actual_count,
+            bid_was_valid,
+            winner,
+        })
+    }
+
+    /// A player loses a die; dropping to one die for the first time makes the next round palifico
+    fn take_die(&mut self, chain_id: &ChainId) {
+        let palifico = self.rules.palifico;
+        if let Some(player) = self.get_player_mut_by_chain(chain_id) {
+            player.lose_die();
+            if palifico && player.dice_count == 1 && !player.had_palifico {
+                player.had_palifico = true;
+                self.next_palifico_player = Some(*chain_id);
+            }
+        }
     }
 
     /// Start a new round
-    pub fn new_round(&mut self) {
+    pub fn new_round(&mut self, timestamp: Timestamp) -> Result<(), GameError> {
+        if self.phase != GamePhase::RoundEnd {
+            return Err(GameError::WrongPhase);
+        }
         self.round += 1;
         self.bid_history.clear();
         self.current_bid = None;
         self.liar_caller = None;
+        self.challenge = ChallengeKind::Liar;
+        self.bid_deadline = None;
         self.reveal_deadline = None;
+        self.commit_deadline = Some(Self::deadline_after(timestamp, self.rules.commit_timeout_micros));
+        self.house_seed_commitment = None;
+        self.house_seed = None;
+        self.round_seed = None;
 
         // Clear revealed dice and commitments
         for player in &mut self.players {
             player.revealed_dice = None;
+            player.revealed_seed = None;
             player.commitment = None;
             player.is_turn = false;
         }
 
-        // Set first non-eliminated player's turn
-        for (i, player) in self.players.iter_mut().enumerate() {
-            if !player.eliminated {
-                player.is_turn = true;
-                self.current_turn = i as u8;
-                break;
-            }
+        if let Some(seat) = self.opener_seat() {
+            self.players[seat].is_turn = true;
+            self.current_turn = seat as u8;
         }
 
+        // Palifico only if the player who triggered it is still in the game
+        self.palifico_player = self
+            .next_palifico_player
+            .take()
+            .filter(|chain_id| self.get_player_by_chain(chain_id).is_some_and(|p| !p.eliminated));
+
         self.phase = GamePhase::Committing;
+        Ok(())
     }
 
-    fn advance_turn(&mut self) -> Result<(), String> {
+    /// Seat that opens the next round: the player picked by `rules.opener` from the last
+    /// round, or the first active player to their left if they are out
+    fn opener_seat(&self) -> Option<usize> {
+        let opener = match self.rules.opener {
+            // A correct calza costs nobody a die - the caller (the winner) opens
+            RoundOpener::Loser => self.last_round_loser.or(self.last_round_winner),
+            RoundOpener::Winner => self.last_round_winner,
+        };
+        let num_players = self.players.len();
+        let start = match opener.and_then(|chain_id| self.players.iter().position(|p| p.chain_id == Some(chain_id))) {
+            Some(seat) => seat,
+            // No previous round to go by: first active seat
+            None => return self.players.iter().position(|p| !p.eliminated),
+        };
+        (0..num_players)
+            .map(|offset| (start + offset) % num_players)
+            .find(|&seat| !self.players[seat].eliminated)
+    }
+
+    /// A player leaves the game; if only one player remains they win
+    pub fn forfeit(&mut self, chain_id: &ChainId) -> Result<(), GameError> {
+        if self.phase == GamePhase::GameOver {
+            return Err(GameError::WrongPhase);
+        }
+        let player = self.get_player_mut_by_chain(chain_id).ok_or(GameError::UnknownPlayer)?;
+        if player.eliminated {
+            return Err(GameError::PlayerEliminated);
+        }
+        player.eliminated = true;
+        player.dice_count = 0;
+        player.result = GameResult::Lost;
+
+        self.settle_eliminations();
+        Ok(())
+    }
+
+    /// Deadline of the current phase, if it has one
+    pub fn phase_deadline(&self) -> Option<Timestamp> {
+        match self.phase {
+            GamePhase::Committing | GamePhase::SharingEntropy => self.commit_deadline,
+            GamePhase::Bidding => self.bid_deadline,
+            GamePhase::Revealing => self.reveal_deadline,
+            _ => None,
+        }
+    }
+
+    fn deadline_after(timestamp: Timestamp, timeout_micros: u64) -> Timestamp {
+        Timestamp::from(timestamp.micros().saturating_add(timeout_micros))
+    }
+
+    /// Penalize whoever is stalling the current phase once its deadline has passed
+    /// - Committing: players without a commitment get `rules.timeout_penalty` and the window reopens
+    /// - SharingEntropy: players who hold back their share are disqualified (they have seen the others' shares
+    ///   and might not like the round seed), so the round seed can be fixed from the rest
+    /// - Bidding: the player on turn gets `rules.timeout_penalty`; losing a die or forfeiting ends the round
+    /// - Revealing: players who have not revealed are disqualified, so the round can be resolved
+    pub fn check_timeout(&mut self, now: Timestamp) -> Result<TimeoutOutcome, GameError> {
+        let deadline = self.phase_deadline().ok_or(GameError::DeadlineNotReached)?;
+        if now.micros() <= deadline.micros() {
+            return Err(GameError::DeadlineNotReached);
+        }
+        let penalty = self.rules.timeout_penalty;
+
+        match self.phase {
+            GamePhase::Committing => {
+                let stalled: Vec<ChainId> = self
+                    .players
+                    .iter()
+                    .filter(|p| !p.eliminated && p.commitment.is_none())
+                    .filter_map(|p| p.chain_id)
+                    .collect();
+                for chain_id in &stalled {
+                    match penalty {
+                        TimeoutPenalty::Forfeit => self.time_out(chain_id),
+                        TimeoutPenalty::LoseDie | TimeoutPenalty::AutoCallLiar => self.take_die(chain_id),
+                    }
+                }
+                if !self.settle_eliminations() {
+                    self.commit_deadline = Some(Self::deadline_after(now, self.rules.commit_timeout_micros));
+                }
+                Ok(TimeoutOutcome::Commit { stalled })
+            }
+            GamePhase::SharingEntropy => {
+                let stalled: Vec<ChainId> = self
+                    .players
+                    .iter()
+                    .filter(|p| !p.eliminated && p.commitment.as_ref().is_none_or(|c| c.entropy.is_none()))
+                    .filter_map(|p| p.chain_id)
+                    .collect();
+                for chain_id in &stalled {
+                    self.time_out(chain_id);
+                }
+                self.settle_eliminations();
+                Ok(TimeoutOutcome::Share { stalled })
+            }
+            GamePhase::Bidding => {
+                let stalled = self
+                    .get_current_player()
+                    .and_then(|p| p.chain_id)
+                    .ok_or(GameError::UnknownPlayer)?;
+                let can_call = self.current_bid.as_ref().is_some_and(|bid| bid.bidder != Some(stalled));
+                if penalty == TimeoutPenalty::AutoCallLiar && can_call {
+                    self.challenge_bid(stalled, ChallengeKind::Liar, now)?;
+                    return Ok(TimeoutOutcome::Bid { stalled, penalty });
+                }
+
+                let penalty = match penalty {
+                    TimeoutPenalty::Forfeit => {
+                        self.time_out(&stalled);
+                        TimeoutPenalty::Forfeit
+                    }
+                    TimeoutPenalty::LoseDie | TimeoutPenalty::AutoCallLiar => {
+                        self.take_die(&stalled);
+                        TimeoutPenalty::LoseDie
+                    }
+                };
+                // The round ends without a challenge; the stalled player counts as its loser
+                self.last_round_loser = Some(stalled);
+                self.last_round_winner = None;
+                self.bid_deadline = None;
+                if !self.settle_eliminations() {
+                    self.phase = GamePhase::RoundEnd;
+                }
+                Ok(TimeoutOutcome::Bid { stalled, penalty })
+            }
+            GamePhase::Revealing => {
+                let stalled: Vec<ChainId> = self
+                    .players
+                    .iter()
+                    .filter(|p| !p.eliminated && !p.commitment.as_ref().is_some_and(|c| c.revealed))
+                    .filter_map(|p| p.chain_id)
+                    .collect();
+                for chain_id in &stalled {
+                    self.time_out(chain_id);
+                }
+                Ok(TimeoutOutcome::Reveal { stalled })
+            }
+            _ => Err(GameError::WrongPhase),
+        }
+    }
+
+    /// Remove a player who missed a deadline
+    fn time_out(&mut self, chain_id: &ChainId) {
+        if let Some(player) = self.get_player_mut_by_chain(chain_id) {
+            player.result = GameResult::TimedOut;
+            player.eliminated = true;
+            player.dice_count = 0;
+        }
+    }
+
+    /// Place newly eliminated players and end the game once at most one player is left
+    /// Players knocked out at the same time share a placement. Returns true if the game is over.
+    pub fn settle_eliminations(&mut self) -> bool {
+        self.total_dice = self.players.iter().map(|p| p.dice_count).sum();
+
+        let active = self.active_player_count();
+        for player in self.players.iter_mut() {
+            if player.eliminated && player.placement == 0 {
+                player.placement = active as u8 + 1;
+            }
+        }
+
+        if active > 1 {
+            return false;
+        }
+        self.commit_deadline = None;
+        self.bid_deadline = None;
+        self.reveal_deadline = None;
+        if let Some(last) = self.players.iter_mut().find(|p| !p.eliminated) {
+            last.placement = 1;
+            last.result = GameResult::Won;
+            self.winner = last.chain_id;
+        }
+        self.phase = GamePhase::GameOver;
+        true
+    }
+
+    /// Final standings with multi-way ELO changes, best placement first
+    pub fn standings(&self) -> Vec<PlayerStanding> {
+        let ratings: Vec<(u32, u8)> = self.players.iter().map(|p| (p.elo, p.placement)).collect();
+        let changes = calculate_multiplayer_elo_changes(&ratings);
+        let mut standings: Vec<PlayerStanding> = self
+            .players
+            .iter()
+            .zip(changes)
+            .map(|(p, elo_change)| {
+                let cheated = p.result == GameResult::Cheater;
+                PlayerStanding {
+                    chain_id: p.chain_id,
+                    name: p.name.clone(),
+                    placement: p.placement,
+                    elo: p.elo,
+                    elo_change: if cheated { elo_change - CHEAT_ELO_PENALTY } else { elo_change },
+                    cheated,
+                }
+            })
+            .collect();
+        standings.sort_by_key(|s| s.placement);
+        standings
+    }
+
+    fn advance_turn(&mut self) -> Result<(), GameError> {
         let num_players = self.players.len();
         let starting_next = (self.current_turn as usize + 1) % num_players;
         let mut next = starting_next;
@@ -420,7 +1642,7 @@ impl LiarsDiceGame {
             // ✅ FIX: Check if we've looped back to start
             if next == starting_next {
                 // All players eliminated - game should end
-                return Err("No active players remaining - all eliminated".to_string());
+                return Err(GameError::NoActivePlayers);
             }
         }
     }
@@ -433,6 +1655,54 @@ impl LiarsDiceGame {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::crypto::{commit_seed, entropy_share};
+
+    fn chain(n: u8) -> ChainId {
+        format!("{:064x}", n).parse().unwrap()
+    }
+
+    /// Two-player game in the bidding phase, with both players committed to their seeds
+    fn bidding_game(house_seed: [u8; 32], seeds: [[u8; 32]; 2]) -> LiarsDiceGame {
+        bidding_game_with_rules(GameRules::default(), house_seed, seeds)
+    }
+
+    fn bidding_game_with_rules(rules: GameRules, house_seed: [u8; 32], seeds: [[u8; 32]; 2]) -> LiarsDiceGame {
+        let mut game = LiarsDiceGame::with_rules(GameId::default(), rules);
+        for i in 0..seeds.len() {
+            let player = GamePlayer {
+                chain_id: Some(chain(i as u8)),
+                ..Default::default()
+            };
+            assert_eq!(game.add_player(player), Ok(()));
+        }
+        assert_eq!(game.start_game(Timestamp::from(0)), Ok(()));
+        start_round_bidding(&mut game, house_seed, seeds);
+        game
+    }
+
+    /// Commit every seat's seed and the house seed, share the entropy, then open the bidding
+    fn start_round_bidding(game: &mut LiarsDiceGame, house_seed: [u8; 32], seeds: [[u8; 32]; 2]) {
+        game.set_house_commitment(commit_seed(&house_seed));
+        for (i, seed) in seeds.iter().enumerate() {
+            assert_eq!(game.commit_dice(&chain(i as u8), commitment(seed)), Ok(()));
+        }
+        assert_eq!(game.start_sharing(Timestamp::from(0)), Ok(()));
+        for (i, seed) in seeds.iter().enumerate() {
+            assert_eq!(game.share_entropy(&chain(i as u8), entropy_share(seed)), Ok(()));
+        }
+        assert_eq!(game.reveal_house_seed(house_seed), Ok(()));
+        assert_eq!(game.start_bidding(Timestamp::from(0)), Ok(()));
+    }
+
+    /// A player's commitment to their dice seed and its entropy share
+    fn commitment(seed: &[u8; 32]) -> DiceCommitment {
+        DiceCommitment::new(commit_seed(seed), commit_seed(&entropy_share(seed)))
+    }
+
+    /// Round seed of a two-player round with both players still in
+    fn round_seed(house_seed: [u8; 32], seeds: [[u8; 32]; 2]) -> [u8; 32] {
+        combine_round_seed(&house_seed, &seeds.map(|seed| entropy_share(&seed)))
+    }
 
     #[test]
     fn test_bid_comparison() {
@@ -464,4 +1734,590 @@ mod tests {
         // Lower in both ways
         assert!(!bid1.is_higher_than(&bid2));
     }
+
+    #[test]
+    fn test_house_seed_must_match_commitment() {
+        let mut game = LiarsDiceGame::new(GameId::default());
+        game.set_house_commitment(commit_seed(&[1u8; 32]));
+
+        assert_eq!(game.reveal_house_seed([2u8; 32]), Err(GameError::HouseSeedMismatch));
+        assert!(game.house_seed.is_none());
+        assert_eq!(game.reveal_house_seed([1u8; 32]), Ok(()));
+    }
+
+    #[test]
+    fn test_entropy_sharing() {
+        let (house_seed, seeds) = ([9u8; 32], [[3u8; 32], [4u8; 32]]);
+        let mut game = LiarsDiceGame::new(GameId::default());
+        for n in 0..3 {
+            let player = GamePlayer { chain_id: Some(chain(n)), ..Default::default() };
+            assert_eq!(game.add_player(player), Ok(()));
+        }
+        assert_eq!(game.start_game(Timestamp::from(0)), Ok(()));
+        game.set_house_commitment(commit_seed(&house_seed));
+        for n in 0..3 {
+            assert_eq!(game.commit_dice(&chain(n), commitment(&[n + 3; 32])), Ok(()));
+        }
+
+        // No share is taken before every seat has committed to theirs
+        assert_eq!(game.phase, GamePhase::Committing);
+        assert_eq!(game.share_entropy(&chain(0), entropy_share(&seeds[0])), Err(GameError::WrongPhase));
+        assert_eq!(game.start_sharing(Timestamp::from(1)), Ok(()));
+        assert_eq!(game.share_entropy(&chain(0), entropy_share(&seeds[0])), Ok(()));
+        assert_eq!(game.share_entropy(&chain(0), entropy_share(&seeds[0])), Err(GameError::AlreadyShared));
+
+        // A share that was not committed to disqualifies
+        assert_eq!(game.share_entropy(&chain(1), [7u8; 32]), Err(GameError::EntropyMismatch));
+        assert_eq!(game.players[1].result, GameResult::Cheater);
+        assert!(!game.all_shared());
+        assert_eq!(game.reveal_house_seed(house_seed), Ok(()));
+        assert_eq!(game.start_bidding(Timestamp::from(2)), Err(GameError::NotAllCommitted));
+
+        // Holding a share back past the deadline disqualifies too
+        let deadline = game.commit_deadline.unwrap().micros();
+        let outcome = game.check_timeout(Timestamp::from(deadline + 1));
+        assert_eq!(outcome, Ok(TimeoutOutcome::Share { stalled: vec![chain(2)] }));
+        assert_eq!(game.phase, GamePhase::GameOver);
+        assert_eq!(game.winner, Some(chain(0)));
+
+        // The round seed folds in every share, so no single seat decides it
+        let mut game = bidding_game(house_seed, seeds);
+        assert_eq!(game.round_seed, Some(round_seed(house_seed, seeds)));
+        assert_ne!(game.round_seed, Some(combine_round_seed(&house_seed, &[entropy_share(&seeds[0])])));
+        game.phase = GamePhase::RoundEnd;
+        assert_eq!(game.new_round(Timestamp::from(0)), Ok(()));
+        assert_eq!(game.round_seed, None);
+    }
+
+    #[test]
+    fn test_forged_dice_are_caught_at_resolution() {
+        let house_seed = [9u8; 32];
+        let seeds = [[3u8; 32], [4u8; 32]];
+        let mut game = bidding_game(house_seed, seeds);
+        let (honest, forger) = (chain(0), chain(1));
+
+        assert_eq!(game.make_bid(Bid::new(2, DiceValue::new(6).unwrap(), honest, Timestamp::from(1))), Ok(()));
+        assert_eq!(game.call_liar(forger, Timestamp::from(2)), Ok(()));
+
+        let round_seed = round_seed(house_seed, seeds);
+        let honest_dice = roll_dice_from_seeds(PlayerDice::STARTING_DICE, game.rules.faces, &seeds[0], &round_seed);
+        let honest_reveal = DiceReveal::new(PlayerDice::from_bytes(&honest_dice).unwrap(), seeds[0]);
+        assert_eq!(game.record_reveal(&honest, honest_reveal), Ok(()));
+
+        // Right seed, but five 6s instead of the dice the combined seed produces
+        let forged_reveal = DiceReveal::new(PlayerDice::from_bytes(&[6; 5]).unwrap(), seeds[1]);
+        assert_eq!(game.record_reveal(&forger, forged_reveal), Ok(()));
+
+        assert!(game.resolve_round().is_ok());
+        assert_eq!(game.get_player_by_chain(&forger).unwrap().result, GameResult::Cheater);
+        assert_eq!(game.phase, GamePhase::GameOver);
+        assert_eq!(game.winner, Some(honest));
+    }
+
+    #[test]
+    fn test_wrong_seed_marks_cheater() {
+        let mut game = bidding_game([9u8; 32], [[3u8; 32], [4u8; 32]]);
+        assert_eq!(game.make_bid(Bid::new(2, DiceValue::new(6).unwrap(), chain(0), Timestamp::from(1))), Ok(()));
+        assert_eq!(game.call_liar(chain(1), Timestamp::from(2)), Ok(()));
+
+        let reveal = DiceReveal::new(PlayerDice::from_bytes(&[6; 5]).unwrap(), [5u8; 32]);
+        assert_eq!(game.record_reveal(&chain(1), reveal), Err(GameError::CommitmentMismatch));
+
+        let player = game.get_player_by_chain(&chain(1)).unwrap();
+        assert!(player.eliminated);
+        assert_eq!(player.result, GameResult::Cheater);
+
+        // The cheat costs a fixed penalty on top of the rated result
+        let ratings: Vec<(u32, u8)> = game.players.iter().map(|p| (p.elo, p.placement)).collect();
+        let fair = calculate_multiplayer_elo_changes(&ratings);
+        let standings = game.standings();
+        let cheater = standings.iter().find(|s| s.chain_id == Some(chain(1))).unwrap();
+        let honest = standings.iter().find(|s| s.chain_id == Some(chain(0))).unwrap();
+        assert!(cheater.cheated && !honest.cheated);
+        assert_eq!(cheater.elo_change, fair[1] - CHEAT_ELO_PENALTY);
+        assert_eq!(honest.elo_change, fair[0]);
+    }
+
+    #[test]
+    fn test_turn_order_enforced() {
+        let mut game = bidding_game([9u8; 32], [[3u8; 32], [4u8; 32]]);
+        let face = DiceValue::new(3).unwrap();
+
+        // Seat 1 cannot open, seat 0 can
+        assert_eq!(game.make_bid(Bid::new(1, face, chain(1), Timestamp::from(1))), Err(GameError::NotYourTurn));
+        assert_eq!(game.make_bid(Bid::new(1, face, chain(0), Timestamp::from(1))), Ok(()));
+
+        // Seat 0 cannot bid twice in a row or call liar on their own bid
+        assert_eq!(game.make_bid(Bid::new(2, face, chain(0), Timestamp::from(2))), Err(GameError::NotYourTurn));
+        assert_eq!(game.call_liar(chain(0), Timestamp::from(2)), Err(GameError::CannotCallOwnBid));
+
+        // Outsiders and bids without a bidder are rejected
+        assert_eq!(game.call_liar(chain(7), Timestamp::from(2)), Err(GameError::UnknownPlayer));
+        let anonymous = Bid { quantity: 2, face, bidder: None, timestamp: None };
+        assert_eq!(game.make_bid(anonymous), Err(GameError::UnknownPlayer));
+
+        // Raises must be higher
+        assert_eq!(game.make_bid(Bid::new(1, face, chain(1), Timestamp::from(2))), Err(GameError::BidNotHigher));
+        assert_eq!(game.call_liar(chain(1), Timestamp::from(2)), Ok(()));
+    }
+
+    #[test]
+    fn test_eliminated_player_cannot_act() {
+        let mut game = bidding_game([9u8; 32], [[3u8; 32], [4u8; 32]]);
+        game.get_player_mut_by_chain(&chain(0)).unwrap().eliminated = true;
+
+        let bid = Bid::new(1, DiceValue::new(3).unwrap(), chain(0), Timestamp::from(1));
+        assert_eq!(game.make_bid(bid), Err(GameError::PlayerEliminated));
+    }
+
+    #[test]
+    fn test_state_transitions_report_errors() {
+        let mut game = LiarsDiceGame::new(GameId::default());
+        let player = |n: u8| GamePlayer {
+            chain_id: Some(chain(n)),
+            dice_count: PlayerDice::STARTING_DICE,
+            ..Default::default()
+        };
+
+        assert_eq!(game.add_player(player(0)), Ok(()));
+        assert_eq!(game.add_player(player(0)), Err(GameError::AlreadyJoined));
+        assert_eq!(game.start_game(Timestamp::from(0)), Err(GameError::NotEnoughPlayers));
+        assert_eq!(game.add_player(player(1)), Ok(()));
+        assert_eq!(game.start_game(Timestamp::from(0)), Ok(()));
+        assert_eq!(game.add_player(player(2)), Err(GameError::WrongPhase));
+
+        let commitment = commitment(&[3u8; 32]);
+        assert_eq!(game.commit_dice(&chain(5), commitment.clone()), Err(GameError::UnknownPlayer));
+        assert_eq!(game.commit_dice(&chain(0), commitment.clone()), Ok(()));
+        assert_eq!(game.commit_dice(&chain(0), commitment), Err(GameError::AlreadyCommitted));
+        assert_eq!(game.start_sharing(Timestamp::from(0)), Err(GameError::NotAllCommitted));
+        assert_eq!(game.start_bidding(Timestamp::from(0)), Err(GameError::WrongPhase));
+        assert_eq!(game.resolve_round(), Err(GameError::WrongPhase));
+    }
+
+    #[test]
+    fn test_rules_drive_the_engine() {
+        let rules = GameRules {
+            wild_ones: false,
+            starting_dice: 3,
+            faces: 8,
+            bid_rule: BidRule::QuantityOnly,
+            opener: RoundOpener::Winner,
+            ..GameRules::default()
+        };
+        let (house_seed, seeds) = ([9u8; 32], [[3u8; 32], [4u8; 32]]);
+        let mut game = bidding_game_with_rules(rules, house_seed, seeds);
+        assert!(game.players.iter().all(|p| p.dice_count == 3));
+        assert_eq!(game.total_dice, 6);
+
+        // Faces beyond the die are invalid; same-quantity raises are not allowed
+        let face = |v: u8| DiceValue::with_faces(v, DiceValue::MAX_FACES).unwrap();
+        assert_eq!(game.make_bid(Bid::new(1, face(9), chain(0), Timestamp::from(1))), Err(GameError::InvalidBid));
+        assert_eq!(game.make_bid(Bid::new(1, face(7), chain(0), Timestamp::from(1))), Ok(()));
+        assert_eq!(game.make_bid(Bid::new(1, face(8), chain(1), Timestamp::from(2))), Err(GameError::BidNotHigher));
+        assert_eq!(game.make_bid(Bid::new(2, face(2), chain(1), Timestamp::from(2))), Ok(()));
+        assert_eq!(game.call_liar(chain(0), Timestamp::from(3)), Ok(()));
+        assert_eq!(game.reveal_deadline, Some(Timestamp::from(3 + game.rules.reveal_timeout_micros)));
+
+        for (i, seed) in seeds.iter().enumerate() {
+            let dice = roll_dice_from_seeds(3, 8, seed, &round_seed(house_seed, seeds));
+            assert!(dice.iter().all(|d| (1..=8).contains(d)));
+            let reveal = DiceReveal::new(PlayerDice::from_bytes(&dice).unwrap(), *seed);
+            assert_eq!(game.record_reveal(&chain(i as u8), reveal), Ok(()));
+        }
+
+        // Without wilds only exact 2s count
+        let exact: u8 = game.players.iter().map(|p| p.revealed_dice.as_ref().unwrap().count_exact(face(2))).sum();
+        let outcome = game.resolve_round().unwrap();
+        assert_eq!(outcome.actual_count, exact);
+
+        // The winner of the challenge opens the next round
+        assert_eq!(game.new_round(Timestamp::from(0)), Ok(()));
+        assert_eq!(game.get_current_player().unwrap().chain_id, Some(outcome.winner));
+    }
+
+    #[test]
+    fn test_invalid_rules_rejected() {
+        let mut game = LiarsDiceGame::with_rules(GameId::default(), GameRules { faces: 1, ..GameRules::default() });
+        for n in 0..2 {
+            let player = GamePlayer { chain_id: Some(chain(n)), ..Default::default() };
+            assert_eq!(game.add_player(player), Ok(()));
+        }
+        assert_eq!(game.start_game(Timestamp::from(0)), Err(GameError::InvalidRules));
+    }
+
+    #[test]
+    fn test_exact_call_gains_or_loses_a_die() {
+        let (house_seed, seeds) = ([9u8; 32], [[3u8; 32], [4u8; 32]]);
+        let face = DiceValue::new(3).unwrap();
+        let round_seed = round_seed(house_seed, seeds);
+        let hands: Vec<PlayerDice> = [(5, seeds[0]), (4, seeds[1])]
+            .iter()
+            .map(|(n, seed)| PlayerDice::from_bytes(&roll_dice_from_seeds(*n, 6, seed, &round_seed)).unwrap())
+            .collect();
+        let count: u8 = hands.iter().map(|d| d.count_face(face, true)).sum();
+        assert!(count > 0);
+
+        // Spot on wins the caller a die back; a miss costs them one
+        for (quantity, expected_dice) in [(count, 5), (count + 1, 3)] {
+            let mut game = bidding_game(house_seed, seeds);
+            game.players[1].dice_count = 4;
+            assert_eq!(game.make_bid(Bid::new(quantity, face, chain(0), Timestamp::from(1))), Ok(()));
+            assert_eq!(game.call_exact(chain(1), Timestamp::from(2)), Ok(()));
+            for (i, dice) in hands.iter().enumerate() {
+                let reveal = DiceReveal::new(dice.clone(), seeds[i]);
+                assert_eq!(game.record_reveal(&chain(i as u8), reveal), Ok(()));
+            }
+
+            let outcome = game.resolve_round().unwrap();
+            assert_eq!(outcome.challenge, ChallengeKind::Exact);
+            assert_eq!(outcome.bid_was_valid, quantity == count);
+            assert_eq!(outcome.gainer.is_some(), quantity == count);
+            assert_eq!(game.players[1].dice_count, expected_dice);
+        }
+
+        // Never above the starting dice
+        let mut player = GamePlayer { dice_count: 5, ..Default::default() };
+        player.gain_die(5);
+        assert_eq!(player.dice_count, 5);
+    }
+
+    /// Rank a bid under the ace-conversion rules: `quantity` aces sit just above
+    /// `2 * quantity` of the highest face
+    fn ace_rank(bid: &Bid) -> (u16, u8) {
+        if bid.face.is_wild() {
+            (2 * bid.quantity as u16, DiceValue::STANDARD_FACES + 1)
+        } else {
+            (bid.quantity as u16, bid.face.value())
+        }
+    }
+
+    #[test]
+    fn test_ace_conversion_raises_from_every_bid() {
+        let ordering = BidRule::AceConversion.ordering();
+        let bid = |quantity: u8, face: u8| Bid {
+            quantity,
+            face: DiceValue::new(face).unwrap(),
+            bidder: None,
+            timestamp: None,
+        };
+
+        for quantity in 1..=15 {
+            for face in 1..=6 {
+                let current = bid(quantity, face);
+                for next_quantity in 1..=31 {
+                    for next_face in 1..=6 {
+                        let next = bid(next_quantity, next_face);
+                        assert_eq!(
+                            ordering.is_raise(&next, &current),
+                            ace_rank(&next) > ace_rank(&current),
+                            "{}x{} after {}x{}",
+                            next_quantity,
+                            next_face,
+                            quantity,
+                            face
+                        );
+                    }
+                }
+            }
+        }
+
+        // Spot checks: halve rounding up to switch to aces, double plus one to switch back
+        assert!(ordering.is_raise(&bid(3, 1), &bid(5, 6)));
+        assert!(!ordering.is_raise(&bid(2, 1), &bid(5, 6)));
+        assert!(ordering.is_raise(&bid(2, 1), &bid(4, 2)));
+        assert!(ordering.is_raise(&bid(7, 2), &bid(3, 1)));
+        assert!(!ordering.is_raise(&bid(6, 6), &bid(3, 1)));
+        assert!(!ordering.is_valid_opening(&bid(2, 1)));
+        assert!(ordering.is_valid_opening(&bid(2, 2)));
+    }
+
+    #[test]
+    fn test_ace_conversion_requires_wild_ones() {
+        let rules = GameRules { bid_rule: BidRule::AceConversion, ..GameRules::default() };
+        assert_eq!(rules.validate(), Ok(()));
+        let rules = GameRules { wild_ones: false, ..rules };
+        assert_eq!(rules.validate(), Err(GameError::InvalidRules));
+    }
+
+    #[test]
+    fn test_palifico_round() {
+        let rules = GameRules { palifico: true, ..GameRules::default() };
+        let (house_seed, seeds) = ([9u8; 32], [[3u8; 32], [4u8; 32]]);
+        let mut game = bidding_game_with_rules(rules, house_seed, seeds);
+        game.players[1].dice_count = 2;
+        let face = |v: u8| DiceValue::new(v).unwrap();
+
+        // Seat 1 calls liar on a bid seat 0 can see is true, and drops to one die
+        let round_seed = round_seed(house_seed, seeds);
+        let hands: Vec<PlayerDice> = [(5, seeds[0]), (2, seeds[1])]
+            .iter()
+            .map(|(n, seed)| PlayerDice::from_bytes(&roll_dice_from_seeds(*n, 6, seed, &round_seed)).unwrap())
+            .collect();
+        let shown = hands[0].dice[0];
+        assert_eq!(game.make_bid(Bid::new(1, shown, chain(0), Timestamp::from(1))), Ok(()));
+        assert_eq!(game.call_liar(chain(1), Timestamp::from(2)), Ok(()));
+        for (i, dice) in hands.iter().enumerate() {
+            assert_eq!(game.record_reveal(&chain(i as u8), DiceReveal::new(dice.clone(), seeds[i])), Ok(()));
+        }
+        let outcome = game.resolve_round().unwrap();
+        assert_eq!(outcome.loser, Some(chain(1)));
+        assert!(!game.is_palifico());
+        assert_eq!(game.new_round(Timestamp::from(0)), Ok(()));
+        assert_eq!(game.palifico_player, Some(chain(1)));
+
+        // The loser opens; the face is locked and only the quantity can rise
+        start_round_bidding(&mut game, house_seed, seeds);
+        assert_eq!(game.make_bid(Bid::new(1, face(4), chain(1), Timestamp::from(3))), Ok(()));
+        assert_eq!(game.make_bid(Bid::new(2, face(5), chain(0), Timestamp::from(4))), Err(GameError::FaceLocked));
+        assert_eq!(game.make_bid(Bid::new(1, face(4), chain(0), Timestamp::from(4))), Err(GameError::BidNotHigher));
+        assert_eq!(game.make_bid(Bid::new(2, face(4), chain(0), Timestamp::from(4))), Ok(()));
+
+        // Ones are not wild
+        game.players[0].revealed_dice = PlayerDice::from_bytes(&[1, 1, 4, 4]);
+        game.players[1].revealed_dice = PlayerDice::from_bytes(&[1]);
+        assert_eq!(game.count_total_dice(face(4), true), 2);
+
+        // Palifico happens once per player
+        game.phase = GamePhase::RoundEnd;
+        assert_eq!(game.new_round(Timestamp::from(0)), Ok(()));
+        assert!(!game.is_palifico());
+
+        // Winning a die back (calza) and dropping to one die again does not trigger another
+        game.players[1].gain_die(5);
+        assert_eq!(game.players[1].dice_count, 2);
+        game.take_die(&chain(1));
+        assert_eq!(game.players[1].dice_count, 1);
+        assert_eq!(game.next_palifico_player, None);
+        game.phase = GamePhase::RoundEnd;
+        assert_eq!(game.new_round(Timestamp::from(0)), Ok(()));
+        assert!(!game.is_palifico());
+    }
+
+    #[test]
+    fn test_round_opener() {
+        let mut game = LiarsDiceGame::new(GameId::default());
+        for n in 0..3 {
+            let player = GamePlayer { chain_id: Some(chain(n)), ..Default::default() };
+            assert_eq!(game.add_player(player), Ok(()));
+        }
+        assert_eq!(game.start_game(Timestamp::from(0)), Ok(()));
+        let open_next_round = |game: &mut LiarsDiceGame| {
+            game.phase = GamePhase::RoundEnd;
+            assert_eq!(game.new_round(Timestamp::from(0)), Ok(()));
+            game.get_current_player().and_then(|p| p.chain_id)
+        };
+
+        // The loser opens
+        game.last_round_loser = Some(chain(1));
+        game.last_round_winner = Some(chain(0));
+        assert_eq!(open_next_round(&mut game), Some(chain(1)));
+
+        // An eliminated loser passes the opening to their left, wrapping around the table
+        game.players[1].eliminated = true;
+        assert_eq!(open_next_round(&mut game), Some(chain(2)));
+        game.last_round_loser = Some(chain(2));
+        game.players[2].eliminated = true;
+        assert_eq!(open_next_round(&mut game), Some(chain(0)));
+
+        // Winner-opens rule
+        game.players[1].eliminated = false;
+        game.rules.opener = RoundOpener::Winner;
+        game.last_round_winner = Some(chain(1));
+        assert_eq!(open_next_round(&mut game), Some(chain(1)));
+    }
+
+    #[test]
+    fn test_placements_and_standings() {
+        let mut game = LiarsDiceGame::new(GameId::default());
+        for n in 0..4 {
+            let player = GamePlayer { chain_id: Some(chain(n)), elo: 1200, ..Default::default() };
+            assert_eq!(game.add_player(player), Ok(()));
+        }
+        assert_eq!(game.start_game(Timestamp::from(0)), Ok(()));
+
+        // First out takes last place
+        assert_eq!(game.forfeit(&chain(2)), Ok(()));
+        assert_eq!(game.get_player_by_chain(&chain(2)).unwrap().placement, 4);
+        assert_eq!(game.phase, GamePhase::Committing);
+
+        // Two players knocked out together share second place
+        game.players[0].eliminated = true;
+        game.players[3].eliminated = true;
+        assert!(game.settle_eliminations());
+        assert_eq!(game.players[0].placement, 2);
+        assert_eq!(game.players[3].placement, 2);
+        assert_eq!(game.winner, Some(chain(1)));
+        assert_eq!(game.players[1].placement, 1);
+        assert_eq!(game.phase, GamePhase::GameOver);
+
+        let standings = game.standings();
+        let order: Vec<_> = standings.iter().map(|s| (s.chain_id, s.placement)).collect();
+        assert_eq!(order, vec![(Some(chain(1)), 1), (Some(chain(0)), 2), (Some(chain(3)), 2), (Some(chain(2)), 4)]);
+        assert!(standings[0].elo_change > 0);
+        assert!(standings[3].elo_change < 0);
+        assert_eq!(standings[1].elo_change, standings[2].elo_change);
+        assert_eq!(standings[0].new_elo(), 1200 + standings[0].elo_change as u32);
+    }
+
+    #[test]
+    fn test_phase_timeouts() {
+        let house_seed = [9u8; 32];
+        let seeds = [[1u8; 32], [2u8; 32]];
+        let face = DiceValue::new(3).unwrap();
+        let rules = GameRules { commit_timeout_micros: 10, bid_timeout_micros: 10, ..GameRules::default() };
+
+        // Committing: the player who never commits loses a die and the window reopens
+        let mut game = LiarsDiceGame::with_rules(GameId::default(), rules.clone());
+        for n in 0..2 {
+            let player = GamePlayer { chain_id: Some(chain(n)), ..Default::default() };
+            assert_eq!(game.add_player(player), Ok(()));
+        }
+        assert_eq!(game.start_game(Timestamp::from(0)), Ok(()));
+        assert_eq!(game.commit_dice(&chain(0), commitment(&seeds[0])), Ok(()));
+        assert_eq!(game.check_timeout(Timestamp::from(10)), Err(GameError::DeadlineNotReached));
+        assert_eq!(game.check_timeout(Timestamp::from(11)), Ok(TimeoutOutcome::Commit { stalled: vec![chain(1)] }));
+        assert_eq!(game.players[1].dice_count, 4);
+        assert_eq!(game.phase, GamePhase::Committing);
+        assert_eq!(game.commit_deadline, Some(Timestamp::from(21)));
+
+        // Bidding, lose a die: the stalled opener loses a die and the round ends
+        let mut game = bidding_game_with_rules(rules.clone(), house_seed, seeds);
+        assert_eq!(game.bid_deadline, Some(Timestamp::from(10)));
+        let outcome = game.check_timeout(Timestamp::from(11));
+        assert_eq!(outcome, Ok(TimeoutOutcome::Bid { stalled: chain(0), penalty: TimeoutPenalty::LoseDie }));
+        assert_eq!(game.phase, GamePhase::RoundEnd);
+        assert_eq!(game.players[0].dice_count, 4);
+        assert_eq!(game.new_round(Timestamp::from(11)), Ok(()));
+        assert_eq!(game.get_current_player().and_then(|p| p.chain_id), Some(chain(0)));
+
+        // Bidding, auto-call: a bid is called on the stalled player's behalf
+        let auto_call = GameRules { timeout_penalty: TimeoutPenalty::AutoCallLiar, ..rules.clone() };
+        let mut game = bidding_game_with_rules(auto_call, house_seed, seeds);
+        assert_eq!(game.make_bid(Bid::new(1, face, chain(0), Timestamp::from(5))), Ok(()));
+        assert_eq!(game.check_timeout(Timestamp::from(15)), Err(GameError::DeadlineNotReached));
+        let outcome = game.check_timeout(Timestamp::from(16));
+        assert_eq!(outcome, Ok(TimeoutOutcome::Bid { stalled: chain(1), penalty: TimeoutPenalty::AutoCallLiar }));
+        assert_eq!(game.phase, GamePhase::Revealing);
+        assert_eq!(game.liar_caller, Some(chain(1)));
+
+        // Revealing: whoever has not revealed is disqualified and the round resolves
+        let round_seed = round_seed(house_seed, seeds);
+        let dice = PlayerDice::from_bytes(&roll_dice_from_seeds(5, 6, &seeds[0], &round_seed)).unwrap();
+        let reveal = DiceReveal::new(dice, seeds[0]);
+        assert_eq!(game.record_reveal(&chain(0), reveal), Ok(()));
+        let deadline = game.reveal_deadline.unwrap().micros();
+        assert_eq!(game.check_timeout(Timestamp::from(deadline + 1)), Ok(TimeoutOutcome::Reveal { stalled: vec![chain(1)] }));
+        assert_eq!(game.players[1].result, GameResult::TimedOut);
+        assert!(game.resolve_round().is_ok());
+        assert_eq!(game.phase, GamePhase::GameOver);
+        assert_eq!(game.winner, Some(chain(0)));
+
+        // Forfeit: a stalled bidder is out of the game
+        let forfeit = GameRules { timeout_penalty: TimeoutPenalty::Forfeit, ..rules };
+        let mut game = bidding_game_with_rules(forfeit, house_seed, seeds);
+        let outcome = game.check_timeout(Timestamp::from(11));
+        assert_eq!(outcome, Ok(TimeoutOutcome::Bid { stalled: chain(0), penalty: TimeoutPenalty::Forfeit }));
+        assert_eq!(game.phase, GamePhase::GameOver);
+        assert_eq!(game.winner, Some(chain(1)));
+        assert_eq!(game.phase_deadline(), None);
+    }
+
+    #[test]
+    fn test_rematch_offer() {
+        let mut game = LiarsDiceGame::new(GameId::new(chain(9), 3));
+        for i in 0..2 {
+            let player = GamePlayer::new(chain(i), AccountOwner::CHAIN, format!("p{}", i), 1200);
+            assert_eq!(game.add_player(player), Ok(()));
+        }
+        let mut offer = RematchOffer::new(&game, chain(8), Timestamp::from(10));
+        assert_eq!(offer.players.len(), 2);
+        assert_eq!(offer.game_id.next_rematch().rematch, 1);
+
+        assert_eq!(offer.accept(chain(5), Timestamp::from(11)), Err(GameError::UnknownPlayer));
+        assert_eq!(offer.accept(chain(0), Timestamp::from(11)), Ok(false));
+        assert_eq!(offer.accept(chain(0), Timestamp::from(12)), Ok(false));
+        assert_eq!(offer.accept(chain(1), Timestamp::from(12)), Ok(true));
+
+        let late = Timestamp::from(10 + RematchOffer::WINDOW_MICROS);
+        assert_eq!(offer.accept(chain(1), late), Err(GameError::NoRematchOffer));
+
+        // A cheater is left out of the rematch
+        game.players[1].mark_cheater();
+        let offer = RematchOffer::new(&game, chain(8), Timestamp::from(10));
+        assert_eq!(offer.players.len(), 1);
+        assert_eq!(offer.players[0].chain_id, Some(chain(0)));
+    }
+
+    #[test]
+    fn test_stake_escrow() {
+        let rules = GameRules {
+            stake: Amount::from_tokens(10),
+            rake_bps: 500,
+            ..GameRules::default()
+        };
+        assert_eq!(rules.validate(), Ok(()));
+        let mut escrow = StakeEscrow::new(GameId::default(), &rules);
+        assert_eq!(escrow.deposit(chain(0), Amount::from_tokens(5), Timestamp::from(1)), Err(GameError::StakeMismatch));
+        assert_eq!(escrow.deposit(chain(0), Amount::from_tokens(10), Timestamp::from(1)), Ok(()));
+        let again = escrow.deposit(chain(0), Amount::from_tokens(10), Timestamp::from(2));
+        assert_eq!(again, Err(GameError::AlreadyStaked));
+        assert_eq!(escrow.deposit(chain(1), Amount::from_tokens(10), Timestamp::from(2)), Ok(()));
+
+        // 5% of a 20 token pot
+        assert_eq!(escrow.rake(), Amount::from_tokens(1));
+        assert_eq!(escrow.payouts(Some(chain(1)), &[]), vec![(chain(1), Amount::from_tokens(19))]);
+
+        // A winner who never staked gets nothing; the deposits go back
+        assert_eq!(escrow.payouts(Some(chain(2)), &[]).len(), 2);
+        assert_eq!(escrow.payouts(None, &[])[0], (chain(0), Amount::from_tokens(10)));
+
+        // ...except a cheater's, which goes to the other player
+        assert_eq!(escrow.payouts(None, &[chain(0)]), vec![(chain(1), Amount::from_tokens(20))]);
+
+        let greedy = GameRules { rake_bps: 10_001, ..GameRules::default() };
+        assert_eq!(greedy.validate(), Err(GameError::InvalidRules));
+    }
+
+    #[test]
+    fn test_spectator_view() {
+        let mut view = SpectatorView::new(GameId::default());
+        let mut game = LiarsDiceGame::new(GameId::default());
+        game.round = 1;
+        view.update_game(game.clone());
+        let three = DiceValue::new(3).unwrap();
+        view.record_bid(Bid::new(2, three, chain(0), Timestamp::from(1)));
+        view.record_bid(Bid::new(3, three, chain(1), Timestamp::from(2)));
+        view.record_reveal(chain(0), PlayerDice::from_bytes(&[3, 3]).unwrap());
+        view.record_reveal(chain(1), PlayerDice::from_bytes(&[1, 5]).unwrap());
+        view.round_ended(Some(chain(1)));
+        assert_eq!(view.bids.len(), 2);
+
+        // The showdown stays visible into the next round until someone bids
+        game.round = 2;
+        view.update_game(game);
+        assert!(view.bids.is_empty());
+        assert_eq!(view.reveals.len(), 2);
+        view.record_bid(Bid::new(1, DiceValue::new(2).unwrap(), chain(0), Timestamp::from(3)));
+        assert!(view.reveals.is_empty());
+        assert_eq!(view.last_loser, Some(chain(1)));
+
+        view.game_ended(Some(chain(0)));
+        assert!(view.finished);
+    }
+
+    #[test]
+    fn test_spectator_delay() {
+        let at = Timestamp::from(1_000_000);
+        assert!(SpectatorDelay::Live.is_due(3, at, Some(3), at));
+
+        let rounds = SpectatorDelay::Rounds(2);
+        assert!(!rounds.is_due(3, at, Some(4), at));
+        assert!(rounds.is_due(3, at, Some(5), at));
+        assert!(rounds.is_due(3, at, None, at));
+
+        let seconds = SpectatorDelay::Seconds(30);
+        assert!(!seconds.is_due(3, at, None, Timestamp::from(30_999_999)));
+        assert!(seconds.is_due(3, at, Some(3), Timestamp::from(31_000_000)));
+    }
 }
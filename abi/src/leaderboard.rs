@@ -166,6 +166,9 @@ pub struct SimpleLeaderboardEntry {
     pub games_won: u64,
     pub games_played: u64,  // ✅ FIX: Track total games for proper win_rate calculation
     pub win_rate: u64,
+    /// Games the player was disqualified from for cheating
+    #[serde(default)]
+    pub cheats: u64,
 }
 
 pub fn calculate_simple_ranking(
@@ -203,6 +206,7 @@ pub fn calculate_simple_ranking(
                 games_won,
                 games_played,
                 win_rate,
+                cheats: 0,
             }
         })
         .collect()
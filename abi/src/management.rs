@@ -1,5 +1,7 @@
 // Chain and room management types for Liar's Dice
 
+use crate::game::{GameId, LiarsDiceGame, SpectatorDelay};
+use crate::player::{QueuedPlayer, StakeTier};
 use async_graphql::scalar;
 use async_graphql_derive::SimpleObject;
 use linera_sdk::linera_base_types::{AccountOwner, ChainId, Timestamp};
@@ -70,52 +72,195 @@ impl LobbyChainInfo {
     }
 }
 
-/// Information about a game chain
+/// Information about a game chain in a lobby's pool
+/// `chain_status` is `Inactive` while the chain misses health checks and `MaintenanceMode`
+/// while it is quarantined; only `Active` chains are given new games
 #[derive(Debug, Clone, Default, Deserialize, Eq, PartialEq, Serialize, SimpleObject)]
 pub struct GameChainInfo {
     pub chain_id: Option<ChainId>,
     pub chain_status: ActivityStatus,
-    /// Is this chain currently hosting a game?
-    pub in_use: bool,
-    /// Current game ID if in use
-    pub current_game_id: Option<u64>,
+    /// Being removed: finishes its current games but gets no new ones
+    pub draining: bool,
+    /// Games currently hosted on this chain
+    pub active_games: u32,
+    /// Most games the chain hosts at once
+    pub capacity: u32,
     /// Total games hosted on this chain
     pub games_hosted: u64,
     pub created_at: Option<Timestamp>,
     pub last_update: Option<Timestamp>,
+    /// Last message received from the chain
+    pub last_heartbeat: Option<Timestamp>,
+    /// Last health check ping sent to the chain
+    pub last_ping: Option<Timestamp>,
 }
 
 impl GameChainInfo {
+    /// Games a newly registered chain hosts at once
+    pub const DEFAULT_CAPACITY: u32 = 8;
+
     pub fn new(chain_id: ChainId, current_time: Timestamp) -> Self {
         GameChainInfo {
             chain_id: Some(chain_id),
             chain_status: ActivityStatus::Active,
-            in_use: false,
-            current_game_id: None,
+            draining: false,
+            active_games: 0,
+            capacity: Self::DEFAULT_CAPACITY,
             games_hosted: 0,
             created_at: Some(current_time),
             last_update: Some(current_time),
+            last_heartbeat: None,
+            last_ping: None,
         }
     }
 
-    pub fn start_game(&mut self, game_id: u64, current_time: Timestamp) {
-        self.in_use = true;
-        self.current_game_id = Some(game_id);
+    pub fn start_game(&mut self, current_time: Timestamp) {
+        self.active_games += 1;
         self.games_hosted += 1;
         self.last_update = Some(current_time);
     }
 
     pub fn end_game(&mut self, current_time: Timestamp) {
-        self.in_use = false;
-        self.current_game_id = None;
+        self.active_games = self.active_games.saturating_sub(1);
+        self.last_update = Some(current_time);
+    }
+
+    /// Is this chain hosting any game?
+    pub fn in_use(&self) -> bool {
+        self.active_games > 0
+    }
+
+    /// Seats left for new games
+    pub fn free_slots(&self) -> u32 {
+        self.capacity.saturating_sub(self.active_games)
+    }
+
+    /// Record a message from the chain; a chain that had gone quiet is healthy again
+    pub fn heartbeat(&mut self, current_time: Timestamp) {
+        self.last_heartbeat = Some(current_time);
         self.last_update = Some(current_time);
+        if self.chain_status == ActivityStatus::Inactive {
+            self.chain_status = ActivityStatus::Active;
+        }
+    }
+
+    /// Has the chain failed to answer the last health check ping?
+    pub fn missed_ping(&self) -> bool {
+        match (self.last_ping, self.last_heartbeat) {
+            (Some(ping), Some(heartbeat)) => ping > heartbeat,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Start a health check: mark the chain inactive if it never answered the previous ping
+    pub fn ping(&mut self, current_time: Timestamp) {
+        if self.missed_ping() && self.chain_status == ActivityStatus::Active {
+            self.chain_status = ActivityStatus::Inactive;
+        }
+        self.last_ping = Some(current_time);
     }
 
     pub fn is_available(&self) -> bool {
-        !self.in_use && self.chain_status == ActivityStatus::Active
+        self.free_slots() > 0 && !self.draining && self.chain_status == ActivityStatus::Active
+    }
+}
+
+/// A game in progress on one of the lobby's game chains
+#[derive(Debug, Clone, Default, Deserialize, Eq, PartialEq, Serialize, SimpleObject)]
+pub struct HostedGame {
+    pub game_id: GameId,
+    pub game_chain: Option<ChainId>,
+    /// Room the game was started from (None for matchmaking games)
+    pub room_id: Option<RoomId>,
+    /// Players seated at the game
+    #[serde(default)]
+    pub players: Vec<ChainId>,
+    pub started_at: Option<Timestamp>,
+    /// Deadline of the game's current phase, as last reported by the game chain
+    pub deadline: Option<Timestamp>,
+}
+
+impl HostedGame {
+    pub fn new(game_id: GameId, game_chain: ChainId, players: Vec<ChainId>, current_time: Timestamp) -> Self {
+        HostedGame {
+            game_id,
+            game_chain: Some(game_chain),
+            room_id: None,
+            players,
+            started_at: Some(current_time),
+            deadline: None,
+        }
+    }
+
+    /// Has the game's phase deadline passed?
+    pub fn is_overdue(&self, current_time: Timestamp) -> bool {
+        self.deadline.is_some_and(|deadline| current_time > deadline)
     }
 }
 
+/// Summary of a lobby's game chain pool
+#[derive(Debug, Clone, Default, Deserialize, Eq, PartialEq, Serialize, SimpleObject)]
+pub struct GameChainPoolStatus {
+    /// Registered game chains
+    pub total: u32,
+    /// Chains ready for a new game
+    pub available: u32,
+    /// Chains hosting at least one game
+    pub in_use: u32,
+    /// Games in progress across the pool
+    pub active_games: u32,
+    /// Free game slots on available chains
+    pub free_slots: u32,
+    /// Chains being removed once their game ends
+    pub draining: u32,
+    /// Chains that missed a health check
+    pub inactive: u32,
+    /// Quarantined chains
+    pub maintenance: u32,
+}
+
+impl GameChainPoolStatus {
+    pub fn from_chains<'a>(chains: impl IntoIterator<Item = &'a GameChainInfo>) -> Self {
+        let mut status = GameChainPoolStatus::default();
+        for chain in chains {
+            status.total += 1;
+            if chain.is_available() {
+                status.available += 1;
+                status.free_slots += chain.free_slots();
+            }
+            if chain.in_use() {
+                status.in_use += 1;
+            }
+            status.active_games += chain.active_games;
+            if chain.draining {
+                status.draining += 1;
+            }
+            match chain.chain_status {
+                ActivityStatus::Active => {}
+                ActivityStatus::Inactive => status.inactive += 1,
+                ActivityStatus::MaintenanceMode => status.maintenance += 1,
+            }
+        }
+        status
+    }
+}
+
+/// A game-chain message rejected because it did not come from the chain it claimed to act for
+#[derive(Debug, Clone, Default, Deserialize, Eq, PartialEq, Serialize, SimpleObject)]
+pub struct UnauthorizedAttempt {
+    pub id: u64,
+    /// Chain the message actually came from
+    pub origin: Option<ChainId>,
+    /// Player the message claimed to act for (None for lobby-only messages)
+    pub claimed_player: Option<ChainId>,
+    /// Message that was rejected (e.g. "MakeBid")
+    pub action: String,
+    /// Game the message was addressed to
+    pub game_id: Option<GameId>,
+    pub attempted_at: Option<Timestamp>,
+}
+
 scalar!(RoomType);
 #[derive(Debug, Clone, Default, Deserialize, Eq, Ord, PartialOrd, PartialEq, Serialize)]
 #[repr(u8)]
@@ -124,10 +269,65 @@ pub enum RoomType {
     Public = 0,
     Private {
         password_hash: String,
+        /// Random salt of the room's password hash (public: joining players hash with it)
+        #[serde(default)]
+        salt: String,
     } = 1,
     Ranked = 2,
 }
 
+scalar!(RoomError);
+/// Why the lobby refused a room action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoomError {
+    RoomNotFound,
+    RoomFull,
+    WrongPassword,
+    NotHost,
+    NotInRoom,
+    AlreadyInRoom,
+    NotEnoughPlayers,
+    GameInProgress,
+    InvalidSeatCount,
+    NoFreeGameChain,
+    PlayerBanned,
+    PlayerBusy,
+    TooManyChallenges,
+    WrongStakeTier,
+}
+
+impl std::fmt::Display for RoomError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            RoomError::RoomNotFound => "no such room",
+            RoomError::RoomFull => "the room is full",
+            RoomError::WrongPassword => "wrong room password",
+            RoomError::NotHost => "only the host can do that",
+            RoomError::NotInRoom => "player is not in the room",
+            RoomError::AlreadyInRoom => "player is already in a room",
+            RoomError::NotEnoughPlayers => "not enough players to start",
+            RoomError::GameInProgress => "the room is playing a game",
+            RoomError::InvalidSeatCount => "invalid number of seats",
+            RoomError::NoFreeGameChain => "no free game chain",
+            RoomError::PlayerBanned => "a player is banned from matchmaking",
+            RoomError::PlayerBusy => "a player is already in a game or room",
+            RoomError::TooManyChallenges => "too many open challenges",
+            RoomError::WrongStakeTier => "the game is played at another stake tier",
+        };
+        f.write_str(msg)
+    }
+}
+
+/// A room action the lobby refused, as reported back to the player who sent it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct RejectedRoomAction {
+    pub room_id: Option<RoomId>,
+    /// The rejected action (e.g. "JoinRoom")
+    pub action: String,
+    pub error: RoomError,
+    pub rejected_at: Option<Timestamp>,
+}
+
 /// Information about a game room
 #[derive(Debug, Clone, Default, Deserialize, Eq, PartialEq, Serialize, SimpleObject)]
 pub struct RoomInfo {
@@ -137,12 +337,23 @@ pub struct RoomInfo {
     pub room_status: ActivityStatus,
     /// Game chain assigned to this room
     pub game_chain: Option<ChainId>,
+    /// Game the room is playing, if any
+    pub current_game: Option<GameId>,
     /// Players currently in this room
     pub player_count: u8,
     /// Maximum players allowed
     pub max_players: u8,
     /// Host/creator of the room
     pub host: Option<AccountOwner>,
+    /// Chain of the host (the only chain allowed to kick, resize or start)
+    pub host_chain: Option<ChainId>,
+    /// Seated players, host first
+    pub players: Vec<QueuedPlayer>,
+    /// How far the spectator feed of the room's games lags behind
+    pub spectator_delay: SpectatorDelay,
+    /// Buy-in of the room's games, chosen by the host (players join at the same tier)
+    #[serde(default)]
+    pub stake_tier: StakeTier,
     /// Total games played in this room
     pub games_played: u64,
     pub created_at: Option<Timestamp>,
@@ -154,7 +365,7 @@ impl RoomInfo {
         room_id: RoomId,
         name: String,
         room_type: RoomType,
-        host: AccountOwner,
+        host: QueuedPlayer,
         max_players: u8,
         current_time: Timestamp,
     ) -> Self {
@@ -164,43 +375,201 @@ impl RoomInfo {
             room_type,
             room_status: ActivityStatus::Active,
             game_chain: None,
+            current_game: None,
             player_count: 1, // Host is first player
             max_players,
-            host: Some(host),
+            host: Some(host.owner),
+            host_chain: Some(host.chain_id),
+            stake_tier: host.stake_tier,
+            players: vec![host],
+            spectator_delay: SpectatorDelay::Live,
             games_played: 0,
             created_at: Some(current_time),
             last_update: Some(current_time),
         }
     }
 
+    /// Is `max_players` a seat count a room can have?
+    pub fn is_valid_seat_count(max_players: u8) -> bool {
+        (LiarsDiceGame::MIN_PLAYERS..=LiarsDiceGame::MAX_PLAYERS).contains(&(max_players as usize))
+    }
+
     pub fn is_joinable(&self) -> bool {
-        self.room_status == ActivityStatus::Active && self.player_count < self.max_players
+        self.room_status == ActivityStatus::Active
+            && self.player_count < self.max_players
+            && self.current_game.is_none()
+    }
+
+    pub fn is_host(&self, chain_id: ChainId) -> bool {
+        self.host_chain == Some(chain_id)
+    }
+
+    pub fn has_player(&self, chain_id: ChainId) -> bool {
+        self.players.iter().any(|p| p.chain_id == chain_id)
     }
 
-    pub fn add_player(&mut self, current_time: Timestamp) -> bool {
+    /// Check a joining player's password hash against the room's (private rooms only)
+    pub fn check_password(&self, password_hash: Option<&str>) -> Result<(), RoomError> {
+        match &self.room_type {
+            RoomType::Private { password_hash: expected, .. } if password_hash != Some(expected.as_str()) => {
+                Err(RoomError::WrongPassword)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn add_player(&mut self, player: QueuedPlayer, current_time: Timestamp) -> Result<(), RoomError> {
+        if self.has_player(player.chain_id) {
+            return Err(RoomError::AlreadyInRoom);
+        }
+        if self.current_game.is_some() {
+            return Err(RoomError::GameInProgress);
+        }
         if !self.is_joinable() {
-            return false;
+            return Err(RoomError::RoomFull);
+        }
+        if player.stake_tier != self.stake_tier {
+            return Err(RoomError::WrongStakeTier);
+        }
+        self.players.push(player);
+        self.player_count = self.players.len() as u8;
+        self.last_update = Some(current_time);
+        Ok(())
+    }
+
+    /// Remove a player; the next player in line becomes host if the host left
+    pub fn remove_player(&mut self, chain_id: ChainId, current_time: Timestamp) -> Result<(), RoomError> {
+        if !self.has_player(chain_id) {
+            return Err(RoomError::NotInRoom);
+        }
+        self.players.retain(|p| p.chain_id != chain_id);
+        self.player_count = self.players.len() as u8;
+        if self.is_host(chain_id) {
+            self.host = self.players.first().map(|p| p.owner);
+            self.host_chain = self.players.first().map(|p| p.chain_id);
         }
-        self.player_count += 1;
         self.last_update = Some(current_time);
-        true
+        Ok(())
     }
 
-    pub fn remove_player(&mut self, current_time: Timestamp) {
-        if self.player_count > 0 {
-            self.player_count -= 1;
+    pub fn set_max_players(&mut self, max_players: u8, current_time: Timestamp) -> Result<(), RoomError> {
+        if !Self::is_valid_seat_count(max_players) || max_players < self.player_count {
+            return Err(RoomError::InvalidSeatCount);
         }
+        self.max_players = max_players;
         self.last_update = Some(current_time);
+        Ok(())
     }
 
-    pub fn assign_game_chain(&mut self, chain_id: ChainId, current_time: Timestamp) {
+    pub fn assign_game_chain(&mut self, chain_id: ChainId, game_id: GameId, current_time: Timestamp) {
         self.game_chain = Some(chain_id);
+        self.current_game = Some(game_id);
         self.last_update = Some(current_time);
     }
 
     pub fn game_ended(&mut self, current_time: Timestamp) {
         self.games_played += 1;
         self.game_chain = None;
+        self.current_game = None;
         self.last_update = Some(current_time);
     }
+
+    /// The room as shown to players: a private room's password hash is never published
+    pub fn redacted(&self) -> RoomInfo {
+        let mut room = self.clone();
+        if let RoomType::Private { password_hash, .. } = &mut room.room_type {
+            password_hash.clear();
+        }
+        room
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_chain_lifecycle() {
+        let chain_id: ChainId = format!("{:064x}", 1).parse().unwrap();
+        let mut info = GameChainInfo::new(chain_id, Timestamp::from(0));
+        assert!(info.is_available());
+
+        // A chain keeps taking games until it is full
+        info.capacity = 2;
+        info.start_game(Timestamp::from(1));
+        assert!(info.in_use());
+        assert!(info.is_available());
+        info.start_game(Timestamp::from(1));
+        assert!(!info.is_available());
+        info.end_game(Timestamp::from(2));
+        assert!(info.is_available());
+        assert_eq!(info.free_slots(), 1);
+        info.end_game(Timestamp::from(2));
+        assert!(!info.in_use());
+        assert_eq!(info.games_hosted, 2);
+
+        // An unanswered ping marks the chain inactive at the next check; any message revives it
+        info.ping(Timestamp::from(3));
+        assert!(info.missed_ping());
+        assert!(info.is_available());
+        info.ping(Timestamp::from(4));
+        assert_eq!(info.chain_status, ActivityStatus::Inactive);
+        assert!(!info.is_available());
+        info.heartbeat(Timestamp::from(5));
+        assert!(!info.missed_ping());
+        assert!(info.is_available());
+
+        // Quarantined chains stay out of rotation even when they answer
+        info.chain_status = ActivityStatus::MaintenanceMode;
+        info.heartbeat(Timestamp::from(6));
+        assert!(!info.is_available());
+
+        let mut draining = GameChainInfo::new(chain_id, Timestamp::from(0));
+        draining.draining = true;
+        let status = GameChainPoolStatus::from_chains([&info, &draining]);
+        assert_eq!(status.total, 2);
+        assert_eq!(status.available, 0);
+        assert_eq!(status.draining, 1);
+        assert_eq!(status.maintenance, 1);
+    }
+
+    #[test]
+    fn test_private_room() {
+        let chain = |n: u64| -> ChainId { format!("{:064x}", n).parse().unwrap() };
+        let owner = AccountOwner::CHAIN;
+        let player = |n: u64| QueuedPlayer::new(chain(n), owner, format!("p{}", n), 1200, 2, Timestamp::from(0));
+        let salt = "salt".to_string();
+        let password_hash = crate::crypto::hash_room_password(&salt, "secret");
+        let room_type = RoomType::Private { password_hash: password_hash.clone(), salt: salt.clone() };
+        let mut room = RoomInfo::new(1, "table".to_string(), room_type, player(1), 3, Timestamp::from(0));
+
+        assert_eq!(room.check_password(None), Err(RoomError::WrongPassword));
+        let wrong = crate::crypto::hash_room_password(&salt, "guess");
+        assert_eq!(room.check_password(Some(&wrong)), Err(RoomError::WrongPassword));
+        let unsalted = crate::crypto::hash_room_password("", "secret");
+        assert_eq!(room.check_password(Some(&unsalted)), Err(RoomError::WrongPassword));
+        assert_eq!(room.check_password(Some(&password_hash)), Ok(()));
+        assert_eq!(room.redacted().room_type, RoomType::Private { password_hash: String::new(), salt });
+
+        let mut high_roller = player(2);
+        high_roller.stake_tier = StakeTier::High;
+        assert_eq!(room.add_player(high_roller, Timestamp::from(1)), Err(RoomError::WrongStakeTier));
+        room.add_player(player(2), Timestamp::from(1)).unwrap();
+        assert_eq!(room.add_player(player(2), Timestamp::from(1)), Err(RoomError::AlreadyInRoom));
+        assert_eq!(room.set_max_players(1, Timestamp::from(1)), Err(RoomError::InvalidSeatCount));
+        room.add_player(player(3), Timestamp::from(1)).unwrap();
+        assert_eq!(room.add_player(player(4), Timestamp::from(1)), Err(RoomError::RoomFull));
+
+        // The next player in line takes over when the host leaves
+        room.remove_player(chain(1), Timestamp::from(2)).unwrap();
+        assert!(room.is_host(chain(2)));
+        assert_eq!(room.player_count, 2);
+        assert_eq!(room.remove_player(chain(1), Timestamp::from(2)), Err(RoomError::NotInRoom));
+
+        room.assign_game_chain(chain(7), GameId::new(chain(8), 0), Timestamp::from(3));
+        assert_eq!(room.add_player(player(4), Timestamp::from(3)), Err(RoomError::GameInProgress));
+        room.game_ended(Timestamp::from(4));
+        assert!(room.is_joinable());
+        assert_eq!(room.games_played, 1);
+    }
 }
@@ -1,5 +1,6 @@
 // Player profile and ELO rating for Liar's Dice
 
+use crate::game::LiarsDiceGame;
 use async_graphql::scalar;
 use async_graphql_derive::SimpleObject;
 use linera_sdk::linera_base_types::{AccountOwner, Amount, ChainId, Timestamp};
@@ -8,9 +9,24 @@ use serde::{Deserialize, Serialize};
 /// Starting ELO rating for new players
 pub const STARTING_ELO: u32 = 1200;
 
+/// How long a direct challenge waits for an answer before it expires
+pub const CHALLENGE_TIMEOUT_MICROS: u64 = 120_000_000; // 2 minutes
+
+/// How many challenges a player can have waiting for an answer at once
+pub const MAX_OPEN_CHALLENGES: usize = 3;
+
 /// K-factor for ELO calculations (determines rating volatility)
 pub const ELO_K_FACTOR: f64 = 32.0;
 
+/// ELO taken from a player disqualified for cheating, on top of their result
+pub const CHEAT_ELO_PENALTY: i32 = 50;
+
+/// Cheat strikes after which the lobby bans a player from matchmaking
+pub const CHEAT_STRIKE_LIMIT: u32 = 3;
+
+/// How long a matchmaking ban lasts (restarted by every further strike)
+pub const CHEAT_BAN_MICROS: u64 = 86_400_000_000; // 24 hours
+
 scalar!(UserStatus);
 /// User status in the system
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -44,6 +60,9 @@ pub struct PlayerProfile {
     pub created_at: Option<Timestamp>,
     /// Last active timestamp
     pub last_active: Option<Timestamp>,
+    /// Games this player was disqualified from for cheating
+    #[serde(default)]
+    pub cheat_strikes: u32,
 }
 
 impl PlayerProfile {
@@ -58,6 +77,7 @@ impl PlayerProfile {
             status: UserStatus::Idle,
             created_at: Some(timestamp),
             last_active: Some(timestamp),
+            cheat_strikes: 0,
         }
     }
 
@@ -192,6 +212,34 @@ pub fn calculate_elo_change(player_elo: u32, opponent_elo: u32, won: bool) -> i3
     change
 }
 
+/// Calculate ELO changes for a multi-player game from each player's (elo, placement)
+/// Every player is scored pairwise against each opponent (better placement wins, equal placement draws)
+/// and the summed change is averaged over the number of opponents, so two-player games match
+/// `calculate_elo_change`.
+pub fn calculate_multiplayer_elo_changes(players: &[(u32, u8)]) -> Vec<i32> {
+    let opponents = players.len().saturating_sub(1).max(1) as i32;
+    players
+        .iter()
+        .enumerate()
+        .map(|(i, &(elo, placement))| {
+            let total: i32 = players
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, &(opponent_elo, opponent_placement))| {
+                    let actual_scaled: i32 = match placement.cmp(&opponent_placement) {
+                        std::cmp::Ordering::Less => 1000,
+                        std::cmp::Ordering::Equal => 500,
+                        std::cmp::Ordering::Greater => 0,
+                    };
+                    32 * (actual_scaled - expected_score_scaled(elo, opponent_elo))
+                })
+                .sum();
+            total / (1000 * opponents)
+        })
+        .collect()
+}
+
 /// Calculate expected score (win probability) scaled by 1000
 /// Uses piecewise linear approximation to avoid powf
 /// The ELO expected score formula: 1 / (1 + 10^(diff/400))
@@ -242,6 +290,64 @@ fn expected_score_scaled(player_elo: u32, opponent_elo: u32) -> i32 {
     }
 }
 
+scalar!(MatchmakingParams);
+/// How the lobby pairs players by rating
+/// A player accepts opponents within `initial_elo_window` of their rating; the window widens by
+/// `window_growth` for every `growth_interval_micros` they have waited, so nobody waits forever
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchmakingParams {
+    /// Rating gap accepted as soon as a player is queued
+    pub initial_elo_window: u32,
+    /// How much the accepted gap widens per interval waited
+    pub window_growth: u32,
+    /// Wait between two widenings of the window
+    pub growth_interval_micros: u64,
+}
+
+impl Default for MatchmakingParams {
+    fn default() -> Self {
+        MatchmakingParams {
+            initial_elo_window: 100,
+            window_growth: 50,
+            growth_interval_micros: 10_000_000, // 10 seconds
+        }
+    }
+}
+
+impl MatchmakingParams {
+    /// Check that the window actually widens over time
+    pub fn is_valid(&self) -> bool {
+        self.growth_interval_micros > 0 && self.window_growth > 0
+    }
+}
+
+scalar!(StakeTier);
+/// Buy-in a player is matched at; players are only seated with others at the same tier
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StakeTier {
+    /// No stake
+    #[default]
+    Free,
+    /// 10 tokens
+    Low,
+    /// 100 tokens
+    Medium,
+    /// 1000 tokens
+    High,
+}
+
+impl StakeTier {
+    /// What each player locks for a game at this tier
+    pub fn stake(&self) -> Amount {
+        match self {
+            StakeTier::Free => Amount::ZERO,
+            StakeTier::Low => Amount::from_tokens(10),
+            StakeTier::Medium => Amount::from_tokens(100),
+            StakeTier::High => Amount::from_tokens(1000),
+        }
+    }
+}
+
 /// Queued player for matchmaking
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
 pub struct QueuedPlayer {
@@ -249,17 +355,35 @@ pub struct QueuedPlayer {
     pub owner: AccountOwner,
     pub name: String,
     pub elo: u32,
+    /// Number of seats this player wants at the table (MIN_PLAYERS..=MAX_PLAYERS)
+    pub table_size: u8,
+    /// Buy-in this player wants to play for
+    #[serde(default)]
+    pub stake_tier: StakeTier,
     pub queued_at: Timestamp,
+    /// Place in the lobby's queue order (assigned by the lobby)
+    pub queue_position: u64,
 }
 
 impl QueuedPlayer {
-    pub fn new(chain_id: ChainId, owner: AccountOwner, name: String, elo: u32, timestamp: Timestamp) -> Self {
+    pub fn new(
+        chain_id: ChainId,
+        owner: AccountOwner,
+        name: String,
+        elo: u32,
+        table_size: u8,
+        timestamp: Timestamp,
+    ) -> Self {
+        let table_size = (table_size as usize).clamp(LiarsDiceGame::MIN_PLAYERS, LiarsDiceGame::MAX_PLAYERS) as u8;
         QueuedPlayer {
             chain_id,
             owner,
             name,
             elo,
+            table_size,
+            stake_tier: StakeTier::Free,
             queued_at: timestamp,
+            queue_position: 0,
         }
     }
 
@@ -271,6 +395,189 @@ impl QueuedPlayer {
             other.elo - self.elo
         }
     }
+
+    /// How long this player has been waiting at `now`
+    pub fn wait_micros(&self, now: Timestamp) -> u64 {
+        now.micros().saturating_sub(self.queued_at.micros())
+    }
+
+    /// Widest rating gap this player accepts after waiting until `now`
+    pub fn elo_window(&self, params: &MatchmakingParams, now: Timestamp) -> u32 {
+        let intervals = self.wait_micros(now) / params.growth_interval_micros.max(1);
+        let growth = params.window_growth.saturating_mul(intervals.min(u32::MAX as u64) as u32);
+        params.initial_elo_window.saturating_add(growth)
+    }
+}
+
+/// Wait times of the players a lobby has seated
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct MatchWaitStats {
+    pub players_matched: u64,
+    pub total_wait_micros: u64,
+    pub longest_wait_micros: u64,
+}
+
+impl MatchWaitStats {
+    pub fn record(&mut self, wait_micros: u64) {
+        self.players_matched += 1;
+        self.total_wait_micros = self.total_wait_micros.saturating_add(wait_micros);
+        self.longest_wait_micros = self.longest_wait_micros.max(wait_micros);
+    }
+
+    pub fn average_wait_micros(&self) -> u64 {
+        self.total_wait_micros.checked_div(self.players_matched).unwrap_or(0)
+    }
+}
+
+/// Snapshot of a lobby's queue and how long its players have waited
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct QueueStats {
+    pub players_waiting: u32,
+    /// Wait of the player queued longest
+    pub oldest_wait_micros: u64,
+    pub average_wait_micros: u64,
+    /// Players seated so far
+    pub players_matched: u64,
+    /// Average wait of the seated players before their table filled
+    pub average_match_wait_micros: u64,
+    pub longest_match_wait_micros: u64,
+}
+
+impl QueueStats {
+    pub fn new(queue: &[QueuedPlayer], matched: &MatchWaitStats, now: Timestamp) -> Self {
+        let waits: Vec<u64> = queue.iter().map(|p| p.wait_micros(now)).collect();
+        QueueStats {
+            players_waiting: waits.len() as u32,
+            oldest_wait_micros: waits.iter().copied().max().unwrap_or(0),
+            average_wait_micros: waits.iter().sum::<u64>().checked_div(waits.len() as u64).unwrap_or(0),
+            players_matched: matched.players_matched,
+            average_match_wait_micros: matched.average_wait_micros(),
+            longest_match_wait_micros: matched.longest_wait_micros,
+        }
+    }
+}
+
+/// Cheating record the lobby keeps for a player
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct CheatRecord {
+    pub strikes: u32,
+    pub last_strike_at: Option<Timestamp>,
+    /// Matchmaking is refused until then
+    pub banned_until: Option<Timestamp>,
+}
+
+impl CheatRecord {
+    /// Count a strike; from `CHEAT_STRIKE_LIMIT` strikes on, each one (re)starts a ban
+    pub fn record_strike(&mut self, now: Timestamp) {
+        self.strikes += 1;
+        self.last_strike_at = Some(now);
+        if self.strikes >= CHEAT_STRIKE_LIMIT {
+            self.banned_until = Some(Timestamp::from(now.micros().saturating_add(CHEAT_BAN_MICROS)));
+        }
+    }
+
+    pub fn is_banned(&self, now: Timestamp) -> bool {
+        self.banned_until.is_some_and(|until| now < until)
+    }
+}
+
+scalar!(ChallengeStatus);
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChallengeStatus {
+    #[default]
+    Pending,
+    Accepted,
+    Declined,
+    Expired,
+}
+
+/// A direct challenge from one player to another, held by the lobby until answered
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, SimpleObject)]
+pub struct Challenge {
+    /// Assigned by the lobby
+    pub challenge_id: u64,
+    pub challenger: QueuedPlayer,
+    pub target_chain: ChainId,
+    pub status: ChallengeStatus,
+    pub issued_at: Timestamp,
+    pub expires_at: Timestamp,
+}
+
+impl Challenge {
+    pub fn new(challenge_id: u64, challenger: QueuedPlayer, target_chain: ChainId, now: Timestamp) -> Self {
+        Challenge {
+            challenge_id,
+            challenger,
+            target_chain,
+            status: ChallengeStatus::Pending,
+            issued_at: now,
+            expires_at: Timestamp::from(now.micros().saturating_add(CHALLENGE_TIMEOUT_MICROS)),
+        }
+    }
+
+    /// Has the challenge gone unanswered past its expiry?
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        self.status == ChallengeStatus::Pending && now >= self.expires_at
+    }
+
+    /// Is this player one of the two sides of the challenge?
+    pub fn involves(&self, chain_id: ChainId) -> bool {
+        self.challenger.chain_id == chain_id || self.target_chain == chain_id
+    }
+}
+
+/// Pick the players for the next table from the matchmaking queue
+/// The earliest-queued player who can fill a table of their preferred size is seated with the
+/// closest-rated players wanting that size and stake tier. Every pair at the table must sit inside
+/// either player's rating window (see `MatchmakingParams`), so the whole table's spread fits, not
+/// just each player's gap to the first; returns the queue indices of the seated players in queue order.
+pub fn next_table(queue: &[QueuedPlayer], params: &MatchmakingParams, now: Timestamp) -> Option<Vec<usize>> {
+    for (head_index, head) in queue.iter().enumerate() {
+        let size = head.table_size as usize;
+        // Everyone who could share the head's table, in rating order (longest waiting first on a tie)
+        let mut bucket: Vec<usize> = queue
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.table_size == head.table_size && p.stake_tier == head.stake_tier)
+            .map(|(i, _)| i)
+            .collect();
+        if bucket.len() < size {
+            continue;
+        }
+        bucket.sort_by_key(|&i| (queue[i].elo, i));
+        let Some(head_pos) = bucket.iter().position(|&i| i == head_index) else {
+            continue;
+        };
+
+        // The closest-rated tables are runs of neighbours in rating order: try each run holding the head
+        // and keep the narrowest one that fits (the longest waiting on a tie)
+        let first = head_pos.saturating_sub(size - 1);
+        let last = head_pos.min(bucket.len() - size);
+        let table = (first..=last)
+            .map(|start| &bucket[start..start + size])
+            .filter(|run| fits_rating_windows(queue, run, params, now))
+            .map(|run| {
+                let spread = queue[run[size - 1]].elo - queue[run[0]].elo;
+                let mut seats = run.to_vec();
+                seats.sort_unstable();
+                (spread, seats)
+            })
+            .min();
+        if let Some((_, seats)) = table {
+            return Some(seats);
+        }
+    }
+    None
+}
+
+/// Is every pair of these queued players inside either one's rating window?
+fn fits_rating_windows(queue: &[QueuedPlayer], seats: &[usize], params: &MatchmakingParams, now: Timestamp) -> bool {
+    seats.iter().enumerate().all(|(n, &a)| {
+        seats[n + 1..].iter().all(|&b| {
+            let (a, b) = (&queue[a], &queue[b]);
+            a.elo_distance(b) <= a.elo_window(params, now).max(b.elo_window(params, now))
+        })
+    })
 }
 
 #[cfg(test)]
@@ -311,6 +618,101 @@ mod tests {
         assert!(expected_score_scaled(1400, 1200) > 500);
     }
 
+    #[test]
+    fn test_multiplayer_elo() {
+        // Two players: same as the head-to-head calculation
+        let changes = calculate_multiplayer_elo_changes(&[(1200, 1), (1200, 2)]);
+        assert_eq!(changes, vec![16, -16]);
+
+        // Four equal players: winner gains, last place loses, middle places roughly even out
+        let changes = calculate_multiplayer_elo_changes(&[(1200, 1), (1200, 2), (1200, 3), (1200, 4)]);
+        assert_eq!(changes[0], 16);
+        assert!(changes[1] > 0 && changes[2] < 0);
+        assert_eq!(changes[3], -16);
+
+        // Tied placements are draws against each other
+        let changes = calculate_multiplayer_elo_changes(&[(1200, 1), (1200, 2), (1200, 2)]);
+        assert_eq!(changes[1], changes[2]);
+        assert!(changes[1] < 0);
+    }
+
+    #[test]
+    fn test_next_table() {
+        let owner = AccountOwner::CHAIN;
+        let queued = |n: u8, size: u8| {
+            let chain_id: ChainId = format!("{:064x}", n).parse().unwrap();
+            QueuedPlayer::new(chain_id, owner, format!("p{}", n), STARTING_ELO, size, Timestamp::from(n as u64))
+        };
+        let params = MatchmakingParams::default();
+        let now = Timestamp::from(10);
+
+        // Out-of-range sizes are clamped
+        assert_eq!(queued(1, 0).table_size, 2);
+        assert_eq!(queued(1, 9).table_size, 6);
+
+        // The head wants 3 seats but only two 3-seat players wait; the 2-seat pair is seated instead
+        let queue = vec![queued(1, 3), queued(2, 2), queued(3, 3), queued(4, 2)];
+        assert_eq!(next_table(&queue, &params, now), Some(vec![1, 3]));
+
+        // Once a third 3-seat player arrives the head's table fills first
+        let queue = vec![queued(1, 3), queued(2, 2), queued(3, 3), queued(4, 3)];
+        assert_eq!(next_table(&queue, &params, now), Some(vec![0, 2, 3]));
+
+        assert_eq!(next_table(&queue[..2], &params, now), None);
+
+        // Each stake tier is its own bucket
+        let mut queue = vec![queued(1, 2), queued(2, 2), queued(3, 2)];
+        queue[0].stake_tier = StakeTier::Low;
+        assert_eq!(next_table(&queue, &params, now), Some(vec![1, 2]));
+        queue[1].stake_tier = StakeTier::Medium;
+        assert_eq!(next_table(&queue, &params, now), None);
+        queue[2].stake_tier = StakeTier::Low;
+        assert_eq!(next_table(&queue, &params, now), Some(vec![0, 2]));
+    }
+
+    #[test]
+    fn test_elo_window_matching() {
+        let owner = AccountOwner::CHAIN;
+        let queued = |n: u8, elo: u32, queued_at: u64| {
+            let chain_id: ChainId = format!("{:064x}", n).parse().unwrap();
+            QueuedPlayer::new(chain_id, owner, format!("p{}", n), elo, 2, Timestamp::from(queued_at))
+        };
+        let params = MatchmakingParams::default();
+        let interval = params.growth_interval_micros;
+
+        // The head is paired with the closest rating, not the next in line
+        let queue = vec![queued(1, 1200, 0), queued(2, 1290, 0), queued(3, 1210, 0)];
+        assert_eq!(next_table(&queue, &params, Timestamp::from(0)), Some(vec![0, 2]));
+
+        // Too far apart to play right away...
+        let queue = vec![queued(1, 1200, 0), queued(2, 1500, 0)];
+        assert_eq!(next_table(&queue, &params, Timestamp::from(0)), None);
+
+        // ...but the window widens by 50 per interval waited: 100 + 4 * 50 covers the gap of 300
+        assert_eq!(queue[0].elo_window(&params, Timestamp::from(4 * interval)), 300);
+        assert_eq!(next_table(&queue, &params, Timestamp::from(3 * interval)), None);
+        assert_eq!(next_table(&queue, &params, Timestamp::from(4 * interval)), Some(vec![0, 1]));
+
+        // A bigger table must fit the window as a whole: 1100 and 1300 are each 100 from the head,
+        // but 200 apart, so they cannot sit together
+        let mut table = vec![queued(1, 1200, 0), queued(2, 1100, 0), queued(3, 1300, 0)];
+        table.iter_mut().for_each(|p| p.table_size = 3);
+        assert_eq!(next_table(&table, &params, Timestamp::from(0)), None);
+        let mut player = queued(4, 1250, 0);
+        player.table_size = 3;
+        table.push(player);
+        assert_eq!(next_table(&table, &params, Timestamp::from(0)), Some(vec![0, 2, 3]));
+
+        let mut stats = MatchWaitStats::default();
+        stats.record(2 * interval);
+        stats.record(4 * interval);
+        let snapshot = QueueStats::new(&queue, &stats, Timestamp::from(interval));
+        assert_eq!(snapshot.players_waiting, 2);
+        assert_eq!(snapshot.oldest_wait_micros, interval);
+        assert_eq!(snapshot.average_match_wait_micros, 3 * interval);
+        assert_eq!(snapshot.longest_match_wait_micros, 4 * interval);
+    }
+
     #[test]
     fn test_win_rate() {
         let mut stats = PlayerLifetimeStats::default();
@@ -319,4 +721,35 @@ mod tests {
 
         assert_eq!(stats.win_rate_bps(), 6000); // 60%
     }
+
+    #[test]
+    fn test_challenge_expiry() {
+        let chain = |n: u64| -> ChainId { format!("{:064x}", n).parse().unwrap() };
+        let challenger = QueuedPlayer::new(chain(1), AccountOwner::CHAIN, "a".to_string(), 1200, 2, Timestamp::from(0));
+        let mut challenge = Challenge::new(0, challenger, chain(2), Timestamp::from(5));
+
+        assert!(challenge.involves(chain(1)) && challenge.involves(chain(2)) && !challenge.involves(chain(3)));
+        assert!(!challenge.is_expired(Timestamp::from(4 + CHALLENGE_TIMEOUT_MICROS)));
+        assert!(challenge.is_expired(Timestamp::from(5 + CHALLENGE_TIMEOUT_MICROS)));
+
+        // Answered challenges never expire
+        challenge.status = ChallengeStatus::Accepted;
+        assert!(!challenge.is_expired(Timestamp::from(5 + CHALLENGE_TIMEOUT_MICROS)));
+    }
+
+    #[test]
+    fn test_cheat_strikes_ban() {
+        let mut record = CheatRecord::default();
+        for n in 1..CHEAT_STRIKE_LIMIT {
+            record.record_strike(Timestamp::from(n as u64));
+            assert!(!record.is_banned(Timestamp::from(n as u64)));
+        }
+        record.record_strike(Timestamp::from(100));
+        assert!(record.is_banned(Timestamp::from(100)));
+        assert!(!record.is_banned(Timestamp::from(100 + CHEAT_BAN_MICROS)));
+
+        // Another strike restarts the ban
+        record.record_strike(Timestamp::from(200 + CHEAT_BAN_MICROS));
+        assert!(record.is_banned(Timestamp::from(100 + 2 * CHEAT_BAN_MICROS)));
+    }
 }
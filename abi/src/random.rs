@@ -41,6 +41,35 @@ pub fn roll_dice(count: u8, hash: String, timestamp: String) -> Result<Vec<u8>,
     Ok(dice)
 }
 
+/// Combine a player's seed with the round seed
+/// combined = SHA-256(player_seed || round_seed)
+pub fn combine_seeds(player_seed: &[u8; 32], round_seed: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(player_seed);
+    hasher.update(round_seed);
+    hasher.finalize().into()
+}
+
+/// Round seed: the house seed combined with every seat's entropy share, in seat order
+/// round_seed = SHA-256(house_seed || share_1 || ... || share_n)
+/// Shares are committed before any is disclosed, so nobody knows the round seed while picking their own seed
+pub fn combine_round_seed(house_seed: &[u8; 32], shares: &[[u8; 32]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(house_seed);
+    for share in shares {
+        hasher.update(share);
+    }
+    hasher.finalize().into()
+}
+
+/// Roll a player's hand from their seed and the round seed
+/// The player cannot steer the result (the round seed depends on the other seats' shares),
+/// and anyone holding both seeds can re-derive it
+pub fn roll_dice_from_seeds(count: u8, faces: u8, player_seed: &[u8; 32], round_seed: &[u8; 32]) -> Vec<u8> {
+    let mut rng = StdRng::from_seed(combine_seeds(player_seed, round_seed));
+    (0..count).map(|_| rng.gen_range(1..=faces)).collect()
+}
+
 /// Generate a random 32-byte salt
 pub fn generate_random_salt(hash: String, timestamp: String) -> Result<[u8; 32], getrandom::Error> {
     let mut rng = get_custom_rng(hash, timestamp)?;
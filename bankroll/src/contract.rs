@@ -120,6 +120,38 @@ impl Contract for BankrollContract {
                 log::info!("Sent TokenPot message to target_chain: {:?}, amount: {}", target_chain, amount);
                 BankrollResponse::Ok
             }
+            // * Public Chain
+            BankrollOperation::PayOut { owner, amount, target_chain } => {
+                log::info!("\n\nBankrollOperation::PayOut");
+                // Only an application on this chain (the game holding the stakes) decides what the pool pays
+                let caller = self.runtime.authenticated_caller_id();
+                assert!(caller.is_some(), "BankrollOperation::PayOut must be called by an application");
+                log::info!(
+                    "BankrollOperation::PayOut request from {:?}, paying {} to {:?} on chain: {:?}",
+                    caller,
+                    amount,
+                    owner,
+                    target_chain
+                );
+
+                // Never panic here: the caller is settling a game inside its own message handler
+                let current_token = *self.state.game_token.get();
+                if current_token < amount {
+                    log::error!("Insufficient tokens to pay out. Available: {}, Required: {}", current_token, amount);
+                    return BankrollResponse::Ok;
+                }
+                let remaining_token = current_token.saturating_sub(amount);
+                self.state.game_token.set(remaining_token);
+                log::info!("Payout processed. Token pool: {} -> {}", current_token, remaining_token);
+
+                let paid_at = self.runtime.system_time();
+                self.message_manager(target_chain, BankrollMessage::PaidOut { owner, amount, paid_at });
+
+                // Update current balance to Master Chain
+                let master_chain = self.state.master_chain.get().expect("master_chain not initialized");
+                self.message_manager(master_chain, BankrollMessage::TokenUpdate { amount: remaining_token });
+                BankrollResponse::Ok
+            }
             // * Master Chain
             BankrollOperation::MintToken { chain_id, amount } => {
                 log::info!("\n\nBankrollOperation::MintToken");
@@ -281,6 +313,24 @@ impl Contract for BankrollContract {
 
                 log::info!("Debt {} successfully updated to Paid status", debt_id);
             }
+            BankrollMessage::PaidOut { owner, amount, paid_at } => {
+                log::info!("\n\nBankrollMessage::PaidOut");
+                log::info!(
+                    "BankrollMessage::PaidOut from {:?} amount: {} for {:?} timestamp: {:?}",
+                    origin_chain_id,
+                    amount,
+                    owner,
+                    paid_at
+                );
+
+                // Only sent by `PayOut`, after the origin chain's pool has paid the tokens
+                let balance_async = self.state.accounts.get(&owner).await;
+                let balance = balance_async.expect("unable to get balance").unwrap_or_default();
+                self.state.accounts.insert(&owner, balance.saturating_add(amount)).unwrap_or_else(|_| {
+                    panic!("unable to update {:?} balance", owner);
+                });
+                log::info!("Credited {} to {:?}", amount, owner);
+            }
             // * Master Chain
             BankrollMessage::TokenUpdate { amount } => {
                 log::info!("\n\nBankrollMessage::TokenUpdate");
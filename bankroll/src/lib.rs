@@ -27,6 +27,8 @@ pub enum BankrollOperation {
     UpdateBalance { owner: AccountOwner, amount: Amount },
     NotifyDebt { amount: Amount, target_chain: ChainId },
     TransferTokenPot { amount: Amount, target_chain: ChainId },
+    // * Public Chain (called by the game application, never as a plain operation)
+    PayOut { owner: AccountOwner, amount: Amount, target_chain: ChainId },
     // * Master Chain
     MintToken { chain_id: ChainId, amount: Amount },
 }
@@ -39,6 +41,7 @@ pub enum BankrollMessage {
     TokenPot { amount: Amount },
     // * User Chain
     DebtPaid { debt_id: u64, amount: Amount, paid_at: Timestamp },
+    PaidOut { owner: AccountOwner, amount: Amount, paid_at: Timestamp },
     // * Master Chain
     TokenUpdate { amount: Amount },
 }
@@ -3,18 +3,28 @@
 mod state;
 
 use self::state::LiarsDiceState;
-use abi::crypto::{create_commitment, verify_commitment};
+use abi::crypto::{commit_seed, entropy_share, hash_room_password, hash_to_hex};
 use abi::dice::{DiceCommitment, DiceReveal, DiceValue, PlayerDice};
-use abi::random::{generate_random_salt, roll_dice};
-use abi::game::{Bid, GamePlayer, GamePhase, LiarsDiceGame};
+use abi::random::{generate_random_salt, roll_dice_from_seeds};
+use abi::game::{
+    Bid, ChallengeKind, GameError, GameId, GamePlayer, GamePhase, GameResult, GameRules, LiarsDiceGame, RejectedAction,
+    RematchOffer, RoundOutcome, SpectatorDelay, SpectatorView, StakeEscrow, TimeoutOutcome,
+};
 use abi::leaderboard::SimpleLeaderboardEntry;
-use abi::player::{calculate_elo_change, PlayerProfile, QueuedPlayer, UserStatus, STARTING_ELO};
+use abi::management::{
+    ActivityStatus, GameChainInfo, HostedGame, RejectedRoomAction, RoomError, RoomId, RoomInfo, RoomType,
+    UnauthorizedAttempt,
+};
+use abi::player::{
+    next_table, Challenge, ChallengeStatus, PlayerProfile, QueuedPlayer, StakeTier, UserStatus, MAX_OPEN_CHALLENGES,
+    STARTING_ELO,
+};
 use bankroll::{BankrollAbi, BankrollOperation, BankrollResponse};
 use liars_dice::{
-    LiarsDiceEvent, LiarsDiceMessage, LiarsDiceOperation, LiarsDiceParameters,
-    LIARS_DICE_STREAM_NAME,
+    DelayedSpectatorEvent, LiarsDiceEvent, LiarsDiceMessage, LiarsDiceOperation, LiarsDiceParameters,
+    LIARS_DICE_STREAM_NAME, SPECTATOR_STREAM_NAME,
 };
-use linera_sdk::linera_base_types::{Amount, ApplicationId, ChainId};
+use linera_sdk::linera_base_types::{Amount, ApplicationId, ChainId, Timestamp};
 use linera_sdk::{
     linera_base_types::WithContractAbi,
     views::{RootView, View},
@@ -73,12 +83,10 @@ impl Contract for LiarsDiceContract {
             1 => {
                 // Lobby chain
                 log::info!("Initialized as LOBBY chain");
-                self.state.queue_count.set(0);
             }
             2 => {
                 // Game chain
                 log::info!("Initialized as GAME chain");
-                self.state.game_chain_available.set(true);
                 self.state.games_hosted.set(0);
             }
             3 => {
@@ -115,19 +123,12 @@ impl Contract for LiarsDiceContract {
                 );
             }
 
-            LiarsDiceOperation::FindMatch {} => {
+            LiarsDiceOperation::FindMatch { table_size, stake_tier } => {
                 self.assert_user_chain(chain_type);
-                let profile = self.state.user_profile.get()
-                    .as_ref()
-                    .expect("Profile not set");
-
-                let queued_player = QueuedPlayer::new(
-                    profile.chain_id.expect("No chain ID"),
-                    profile.owner.expect("No owner"),
-                    profile.name.clone(),
-                    profile.elo,
-                    self.runtime.system_time(),
-                );
+                let stake_tier = stake_tier.unwrap_or_default();
+                self.assert_can_stake(stake_tier);
+                let mut queued_player = self.profile_player(table_size.unwrap_or(LiarsDiceGame::MIN_PLAYERS as u8));
+                queued_player.stake_tier = stake_tier;
 
                 // Send to lobby chain
                 if let Some(lobby_chain) = self.state.lobby_chain.get().as_ref() {
@@ -159,15 +160,16 @@ impl Contract for LiarsDiceContract {
                 }
             }
 
-            LiarsDiceOperation::CommitDice { commitment } => {
+            LiarsDiceOperation::CommitDice { commitment, entropy_hash } => {
                 self.assert_user_chain(chain_type);
                 let chain_id = self.runtime.chain_id();
 
-                if let Some(game_chain) = self.state.user_game_chain.get().as_ref() {
-                    let dice_commitment = DiceCommitment::new(commitment);
+                if let Some((game_chain, game_id)) = self.user_game() {
+                    let dice_commitment = DiceCommitment::new(commitment, entropy_hash);
                     self.message_manager(
-                        *game_chain,
+                        game_chain,
                         LiarsDiceMessage::CommitDice {
+                            game_id,
                             player_chain: chain_id,
                             commitment: dice_commitment,
                         },
@@ -176,6 +178,19 @@ impl Contract for LiarsDiceContract {
                 }
             }
 
+            LiarsDiceOperation::ShareEntropy { entropy } => {
+                self.assert_user_chain(chain_type);
+                let chain_id = self.runtime.chain_id();
+
+                if let Some((game_chain, game_id)) = self.user_game() {
+                    self.message_manager(
+                        game_chain,
+                        LiarsDiceMessage::ShareEntropy { game_id, player_chain: chain_id, entropy },
+                    );
+                    log::info!("Sent entropy share to game chain");
+                }
+            }
+
             LiarsDiceOperation::RevealDice { dice, salt } => {
                 self.assert_user_chain(chain_type);
                 let chain_id = self.runtime.chain_id();
@@ -185,10 +200,11 @@ impl Contract for LiarsDiceContract {
                 let salt_array: [u8; 32] = salt;
                 let reveal = DiceReveal::new(player_dice, salt_array);
 
-                if let Some(game_chain) = self.state.user_game_chain.get().as_ref() {
+                if let Some((game_chain, game_id)) = self.user_game() {
                     self.message_manager(
-                        *game_chain,
+                        game_chain,
                         LiarsDiceMessage::RevealDice {
+                            game_id,
                             player_chain: chain_id,
                             reveal,
                         },
@@ -202,11 +218,22 @@ impl Contract for LiarsDiceContract {
                 let chain_id = self.runtime.chain_id();
                 let timestamp = self.runtime.system_time();
 
-                // Validate face value before creating DiceValue
-                let dice_face = match DiceValue::new(face) {
+                // Validate face value against the game's dice before creating DiceValue
+                let faces = self.state.channel_game_state.get()
+                    .as_ref()
+                    .map(|g| g.rules.faces)
+                    .unwrap_or(DiceValue::STANDARD_FACES);
+                let dice_face = match DiceValue::with_faces(face, faces) {
                     Some(dv) => dv,
                     None => {
-                        log::error!("Invalid face value: {}. Must be between 1 and 6.", face);
+                        log::error!("Invalid face value: {}. Must be between 1 and {}.", face, faces);
+                        let game_id = self.state.channel_game_state.get().as_ref().map(|g| g.game_id);
+                        self.state.last_rejection.set(Some(RejectedAction {
+                            game_id,
+                            action: "MakeBid".to_string(),
+                            error: GameError::InvalidBid,
+                            rejected_at: Some(timestamp),
+                        }));
                         return;
                     }
                 };
@@ -218,10 +245,11 @@ impl Contract for LiarsDiceContract {
                     timestamp,
                 );
 
-                if let Some(game_chain) = self.state.user_game_chain.get().as_ref() {
+                if let Some((game_chain, game_id)) = self.user_game() {
                     self.message_manager(
-                        *game_chain,
+                        game_chain,
                         LiarsDiceMessage::MakeBid {
+                            game_id,
                             player_chain: chain_id,
                             bid,
                         },
@@ -233,10 +261,34 @@ impl Contract for LiarsDiceContract {
                 self.assert_user_chain(chain_type);
                 let chain_id = self.runtime.chain_id();
 
-                if let Some(game_chain) = self.state.user_game_chain.get().as_ref() {
+                if let Some((game_chain, game_id)) = self.user_game() {
+                    self.message_manager(
+                        game_chain,
+                        LiarsDiceMessage::CallLiar { game_id, player_chain: chain_id },
+                    );
+                }
+            }
+
+            LiarsDiceOperation::CallExact {} => {
+                self.assert_user_chain(chain_type);
+                let chain_id = self.runtime.chain_id();
+
+                if let Some((game_chain, game_id)) = self.user_game() {
+                    self.message_manager(
+                        game_chain,
+                        LiarsDiceMessage::CallExact { game_id, player_chain: chain_id },
+                    );
+                }
+            }
+
+            LiarsDiceOperation::ClaimTimeout {} => {
+                self.assert_user_chain(chain_type);
+                let chain_id = self.runtime.chain_id();
+
+                if let Some((game_chain, game_id)) = self.user_game() {
                     self.message_manager(
-                        *game_chain,
-                        LiarsDiceMessage::CallLiar { player_chain: chain_id },
+                        game_chain,
+                        LiarsDiceMessage::ClaimTimeout { game_id, player_chain: chain_id },
                     );
                 }
             }
@@ -245,19 +297,68 @@ impl Contract for LiarsDiceContract {
                 self.assert_user_chain(chain_type);
                 let chain_id = self.runtime.chain_id();
 
-                if let Some(game_chain) = self.state.user_game_chain.get().as_ref() {
+                if let Some((game_chain, game_id)) = self.user_game() {
                     self.message_manager(
-                        *game_chain,
-                        LiarsDiceMessage::PlayerForfeit { player_chain: chain_id },
+                        game_chain,
+                        LiarsDiceMessage::PlayerForfeit { game_id, player_chain: chain_id },
                     );
                 }
 
+                // A forfeited stake stays in the pot unless the game ends up refunding it:
+                // the net result is recorded when the game's result arrives
+                let stake = std::mem::take(self.state.user_stake.get_mut());
+                if let (Some(game_id), true) = (*self.state.user_game_id.get(), stake > Amount::ZERO) {
+                    self.state.exited_stakes.insert(&game_id, stake).expect("Failed to store exited stake");
+                }
                 self.state.user_game_chain.set(None);
+                self.state.user_game_id.set(None);
                 if let Some(ref mut profile) = *self.state.user_profile.get_mut() {
                     profile.set_status(UserStatus::Idle);
                 }
             }
 
+            LiarsDiceOperation::RequestRematch {} => {
+                self.assert_user_chain(chain_type);
+                let chain_id = self.runtime.chain_id();
+                match self.state.user_rematch.get().clone() {
+                    Some(RematchOffer {
+                        game_id,
+                        game_chain: Some(game_chain),
+                        ..
+                    }) => {
+                        self.message_manager(
+                            game_chain,
+                            LiarsDiceMessage::RequestRematch { game_id, player_chain: chain_id },
+                        );
+                    }
+                    _ => log::error!("No rematch offer to accept"),
+                }
+            }
+
+            LiarsDiceOperation::SpectateGame { game_chain } => {
+                self.assert_user_chain(chain_type);
+                if self.user_game().is_some() {
+                    log::error!("Cannot spectate while playing a game");
+                    return;
+                }
+                self.stop_spectating();
+
+                // Spectators follow the spectator feed, which lags behind by the game's spectator delay
+                self.state.spectating.set(Some(game_chain));
+                let app_id = self.runtime.application_id().forget_abi();
+                self.runtime.subscribe_to_events(game_chain, app_id, SPECTATOR_STREAM_NAME.into());
+                self.message_manager(game_chain, LiarsDiceMessage::SpectatorJoined);
+                if let Some(ref mut profile) = *self.state.user_profile.get_mut() {
+                    profile.set_status(UserStatus::SpectatingGame { game_chain });
+                }
+                log::info!("Spectating game chain {:?}", game_chain);
+            }
+
+            LiarsDiceOperation::StopSpectating {} => {
+                self.assert_user_chain(chain_type);
+                self.stop_spectating();
+            }
+
             LiarsDiceOperation::GetBalance {} => {
                 self.assert_user_chain(chain_type);
                 let balance = self.bankroll_get_balance();
@@ -279,63 +380,255 @@ impl Contract for LiarsDiceContract {
                 log::info!("InitialSetup: Configured lobby chain {}", lobby_chain);
             }
 
+            LiarsDiceOperation::CreateRoom {
+                name,
+                max_players,
+                password,
+                stake_tier,
+            } => {
+                self.assert_user_chain(chain_type);
+                let stake_tier = stake_tier.unwrap_or_default();
+                self.assert_can_stake(stake_tier);
+                let max_players = max_players.unwrap_or(LiarsDiceGame::MAX_PLAYERS as u8);
+                let mut host = self.profile_player(max_players);
+                host.stake_tier = stake_tier;
+                let lobby_chain = self.get_lobby_chain();
+                let password_hash = password.map(|password| {
+                    let salt = self.draw_room_salt();
+                    (hash_room_password(&salt, &password), salt)
+                });
+                self.message_manager(
+                    lobby_chain,
+                    LiarsDiceMessage::CreateRoom {
+                        host,
+                        name,
+                        max_players,
+                        password_hash,
+                    },
+                );
+            }
+
+            LiarsDiceOperation::JoinRoom {
+                room_id,
+                password,
+                password_salt,
+                stake_tier,
+            } => {
+                self.assert_user_chain(chain_type);
+                let stake_tier = stake_tier.unwrap_or_default();
+                self.assert_can_stake(stake_tier);
+                let mut player = self.profile_player(LiarsDiceGame::MIN_PLAYERS as u8);
+                player.stake_tier = stake_tier;
+                let lobby_chain = self.get_lobby_chain();
+                let password_hash = password.map(|password| {
+                    let salt = password_salt.expect("JoinRoom with a password needs the room's password salt");
+                    hash_room_password(&salt, &password)
+                });
+                self.message_manager(
+                    lobby_chain,
+                    LiarsDiceMessage::JoinRoom {
+                        room_id,
+                        player,
+                        password_hash,
+                    },
+                );
+            }
+
+            LiarsDiceOperation::LeaveRoom {} => {
+                self.assert_user_chain(chain_type);
+                let room_id = self.user_room_id("LeaveRoom");
+                let lobby_chain = self.get_lobby_chain();
+                self.message_manager(lobby_chain, LiarsDiceMessage::LeaveRoom { room_id });
+            }
+
+            LiarsDiceOperation::KickFromRoom { player_chain } => {
+                self.assert_user_chain(chain_type);
+                let room_id = self.user_room_id("KickFromRoom");
+                let lobby_chain = self.get_lobby_chain();
+                self.message_manager(lobby_chain, LiarsDiceMessage::KickFromRoom { room_id, player_chain });
+            }
+
+            LiarsDiceOperation::SetRoomSeats { max_players } => {
+                self.assert_user_chain(chain_type);
+                let room_id = self.user_room_id("SetRoomSeats");
+                let lobby_chain = self.get_lobby_chain();
+                self.message_manager(lobby_chain, LiarsDiceMessage::SetRoomSeats { room_id, max_players });
+            }
+
+            LiarsDiceOperation::StartRoomGame {} => {
+                self.assert_user_chain(chain_type);
+                let room_id = self.user_room_id("StartRoomGame");
+                let lobby_chain = self.get_lobby_chain();
+                self.message_manager(lobby_chain, LiarsDiceMessage::StartRoomGame { room_id });
+            }
+
+            LiarsDiceOperation::SetRoomSpectatorDelay { delay } => {
+                self.assert_user_chain(chain_type);
+                let room_id = self.user_room_id("SetRoomSpectatorDelay");
+                let lobby_chain = self.get_lobby_chain();
+                self.message_manager(lobby_chain, LiarsDiceMessage::SetRoomSpectatorDelay { room_id, delay });
+            }
+
+            LiarsDiceOperation::ChallengePlayer { target_chain, stake_tier } => {
+                self.assert_user_chain(chain_type);
+                let stake_tier = stake_tier.unwrap_or_default();
+                self.assert_can_stake(stake_tier);
+                let mut challenger = self.profile_player(LiarsDiceGame::MIN_PLAYERS as u8);
+                challenger.stake_tier = stake_tier;
+                let lobby_chain = self.get_lobby_chain();
+                self.message_manager(lobby_chain, LiarsDiceMessage::ChallengePlayer { challenger, target_chain });
+            }
+
+            LiarsDiceOperation::AcceptChallenge { challenge_id } => {
+                self.assert_user_chain(chain_type);
+                // The challenger set the stake tier
+                let stake_tier = self.state.user_challenges.get(&challenge_id).await
+                    .expect("Failed to load challenge")
+                    .map(|challenge| challenge.challenger.stake_tier)
+                    .unwrap_or_else(|| panic!("No pending challenge {}", challenge_id));
+                self.assert_can_stake(stake_tier);
+                let mut player = self.profile_player(LiarsDiceGame::MIN_PLAYERS as u8);
+                player.stake_tier = stake_tier;
+                let lobby_chain = self.get_lobby_chain();
+                self.message_manager(lobby_chain, LiarsDiceMessage::AcceptChallenge { challenge_id, player });
+            }
+
+            LiarsDiceOperation::DeclineChallenge { challenge_id } => {
+                self.assert_user_chain(chain_type);
+                let lobby_chain = self.get_lobby_chain();
+                self.message_manager(lobby_chain, LiarsDiceMessage::DeclineChallenge { challenge_id });
+            }
+
             // ============================================
-            // GAME CHAIN OPERATIONS
+            // LOBBY CHAIN OPERATIONS
             // ============================================
-            LiarsDiceOperation::CheckTimeout {} => {
-                self.assert_game_chain(chain_type);
-                log::info!("Checking for reveal timeout");
-
-                let current_time = self.runtime.system_time();
-                let should_resolve = {
-                    if let Some(ref mut game) = *self.state.current_game.get_mut() {
-                        if game.phase == abi::game::GamePhase::Revealing {
-                            if let Some(deadline) = game.reveal_deadline {
-                                if current_time.micros() > deadline.micros() {
-                                    log::info!("Reveal timeout! Eliminating non-revealers");
-
-                                    // Eliminate players who haven't revealed
-                                    for player in &mut game.players {
-                                        if !player.eliminated {
-                                            let revealed = player.commitment
-                                                .as_ref()
-                                                .map(|c| c.revealed)
-                                                .unwrap_or(false);
-
-                                            if !revealed {
-                                                log::info!("Player {:?} eliminated for reveal timeout", player.chain_id);
-                                                if let Some(ref mut commitment) = player.commitment {
-                                                    commitment.revealed = true; // Mark as revealed so all_revealed() works
-                                                }
-                                                player.result = abi::game::GameResult::TimedOut;
-                                                player.eliminated = true;
-                                                player.dice_count = 0;
-                                            }
-                                        }
-                                    }
-
-                                    // Update total dice
-                                    game.total_dice = game.players.iter().map(|p| p.dice_count).sum();
-
-                                    // Check if we can resolve the round now
-                                    true
-                                } else {
-                                    false
-                                }
-                            } else {
-                                false
-                            }
-                        } else {
-                            false
+            LiarsDiceOperation::SetGameRules { rules } => {
+                self.assert_lobby_chain(chain_type);
+                if let Err(error) = rules.validate() {
+                    log::error!("Rejected game rules {:?}: {}", rules, error);
+                    return;
+                }
+                log::info!("Lobby game rules set to {:?}", rules);
+                self.state.game_rules.set(rules);
+            }
+
+            LiarsDiceOperation::SetMatchmakingParams { params } => {
+                self.assert_lobby_chain(chain_type);
+                if !params.is_valid() {
+                    log::error!("Rejected matchmaking params {:?}: the rating window must widen over time", params);
+                    return;
+                }
+                log::info!("Lobby matchmaking params set to {:?}", params);
+                self.state.matchmaking_params.set(params);
+                self.try_match_players().await;
+            }
+
+            LiarsDiceOperation::SweepTimeouts {} => {
+                self.assert_lobby_chain(chain_type);
+                self.sweep_timeouts().await;
+
+                // Rating windows widen with time - waiting players may be matchable now
+                self.try_match_players().await;
+            }
+
+            LiarsDiceOperation::CheckPoolHealth {} => {
+                self.assert_lobby_chain(chain_type);
+                let now = self.runtime.system_time();
+                let game_chains = self.state.game_chains.indices().await.expect("Failed to get game chains");
+
+                // Chains that never answered the last ping go inactive until they are heard from again
+                for game_chain in game_chains {
+                    if let Ok(Some(mut info)) = self.state.game_chains.get(&game_chain).await {
+                        info.ping(now);
+                        if info.chain_status == ActivityStatus::Inactive {
+                            log::error!("Game chain {:?} missed its health check", game_chain);
                         }
-                    } else {
-                        false
+                        self.state.game_chains.insert(&game_chain, info).expect("Failed to update game chain");
+                        self.message_manager(game_chain, LiarsDiceMessage::PoolPing);
+                    }
+                }
+            }
+
+            LiarsDiceOperation::SetGameChainStatus { chain_id, status } => {
+                self.assert_lobby_chain(chain_type);
+                let mut info = match self.state.game_chains.get(&chain_id).await.expect("Failed to load game chain") {
+                    Some(info) => info,
+                    None => {
+                        log::error!("Game chain {:?} is not in the pool", chain_id);
+                        return;
                     }
                 };
+                log::info!("Game chain {:?} status {:?} -> {:?}", chain_id, info.chain_status, status);
+                info.chain_status = status;
+                info.last_update = Some(self.runtime.system_time());
+                self.push_pool_capacity(&info);
+                self.state.game_chains.insert(&chain_id, info).expect("Failed to update game chain");
 
-                if should_resolve {
-                    self.resolve_round().await;
+                // A chain back in service may seat waiting players
+                self.try_match_players().await;
+            }
+
+            LiarsDiceOperation::RemoveGameChain { chain_id } => {
+                self.assert_lobby_chain(chain_type);
+                let mut info = match self.state.game_chains.get(&chain_id).await.expect("Failed to load game chain") {
+                    Some(info) => info,
+                    None => {
+                        log::error!("Game chain {:?} is not in the pool", chain_id);
+                        return;
+                    }
+                };
+                if info.in_use() {
+                    // Let the hosted games finish; the chain leaves the pool when the last one reports GameEnded
+                    log::info!("Draining game chain {:?} ({} games left)", chain_id, info.active_games);
+                    info.draining = true;
+                    info.last_update = Some(self.runtime.system_time());
+                    self.push_pool_capacity(&info);
+                    self.state.game_chains.insert(&chain_id, info).expect("Failed to update game chain");
+                } else {
+                    log::info!("Removed game chain {:?} from the pool", chain_id);
+                    self.state.game_chains.remove(&chain_id).expect("Failed to remove game chain");
+                }
+            }
+
+            LiarsDiceOperation::SetGameChainCapacity { chain_id, capacity } => {
+                self.assert_lobby_chain(chain_type);
+                if capacity == 0 {
+                    log::error!("Rejected zero capacity for game chain {:?}", chain_id);
+                    return;
+                }
+                let mut info = match self.state.game_chains.get(&chain_id).await.expect("Failed to load game chain") {
+                    Some(info) => info,
+                    None => {
+                        log::error!("Game chain {:?} is not in the pool", chain_id);
+                        return;
+                    }
+                };
+                // Games above a lowered capacity play on; the chain just takes no new ones until below it
+                log::info!("Game chain {:?} capacity {} -> {}", chain_id, info.capacity, capacity);
+                info.capacity = capacity;
+                info.last_update = Some(self.runtime.system_time());
+                self.push_pool_capacity(&info);
+                self.state.game_chains.insert(&chain_id, info).expect("Failed to update game chain");
+
+                self.try_match_players().await;
+            }
+
+            // ============================================
+            // GAME CHAIN OPERATIONS
+            // ============================================
+            LiarsDiceOperation::CheckTimeout { game_id } => {
+                self.assert_game_chain(chain_type);
+                log::info!("Checking game {} for phase timeout", game_id);
+
+                if let Err(error) = self.handle_timeout(game_id).await {
+                    log::info!("No timeout to handle: {}", error);
                 }
+                self.release_spectator_events().await;
+            }
+
+            LiarsDiceOperation::ReleaseSpectatorFeed {} => {
+                self.assert_game_chain(chain_type);
+                self.release_spectator_events().await;
             }
 
             // ============================================
@@ -375,6 +668,11 @@ impl Contract for LiarsDiceContract {
         let chain_type = *self.state.chain_type.get();
         let origin = self.runtime.message_origin_chain_id().expect("No origin chain");
 
+        // Any message to a game chain moves the delayed spectator feed along
+        if chain_type == 2 {
+            self.release_spectator_events().await;
+        }
+
         match message {
             // ============================================
             // SUBSCRIPTION CONTROL (Universal)
@@ -399,82 +697,151 @@ impl Contract for LiarsDiceContract {
             LiarsDiceMessage::MatchFound {
                 game_chain,
                 game_id,
-                opponent_name,
-                opponent_elo,
+                opponents,
             } => {
                 self.assert_user_chain(chain_type);
-                log::info!(
-                    "Match found! Game: {}, Opponent: {} (ELO: {})",
-                    game_id, opponent_name, opponent_elo
-                );
+                let names: Vec<String> = opponents.iter()
+                    .map(|o| format!("{} (ELO: {})", o.name, o.elo))
+                    .collect();
+                log::info!("Match found! Game: {}, Opponents: {}", game_id, names.join(", "));
 
+                self.stop_spectating();
                 self.state.user_game_chain.set(Some(game_chain));
+                self.state.user_game_id.set(Some(game_id));
+                self.state.user_rematch.set(None);
 
                 if let Some(ref mut profile) = *self.state.user_profile.get_mut() {
                     profile.set_status(UserStatus::InGame { game_chain });
                 }
-
-                // Subscribe to game chain events
-                let app_id = self.runtime.application_id().forget_abi();
-                self.runtime.subscribe_to_events(game_chain, app_id, LIARS_DICE_STREAM_NAME.into());
             }
 
             LiarsDiceMessage::GameStarted { game } => {
                 self.assert_user_chain(chain_type);
+                // The game chain can beat the lobby's MatchFound here (and a rematch has none) -
+                // adopt the game if we are not in one and are waiting for it
+                if self.state.user_game_id.get().is_none() && self.expects_game(origin, game.game_id).await {
+                    self.stop_spectating();
+                    self.state.user_game_chain.set(Some(origin));
+                    self.state.user_game_id.set(Some(game.game_id));
+                    self.state.user_rematch.set(None);
+                    if let Some(ref mut profile) = *self.state.user_profile.get_mut() {
+                        profile.set_status(UserStatus::InGame { game_chain: origin });
+                    }
+                }
+                if !self.is_current_game(game.game_id, "GameStarted") {
+                    return;
+                }
                 log::info!("Game started: {:?}", game.game_id);
                 self.state.channel_game_state.set(Some(game.clone()));
 
-                // Generate dice and salt for this round
-                let chain_id = self.runtime.chain_id();
-                let timestamp = self.runtime.system_time();
-                let nonce = *self.state.rng_nonce.get();
-                // Mix private nonce into seed so observers can't predict dice
-                let seed_str = format!("{:?}_{}_{}", chain_id, timestamp.micros(), nonce);
-                self.state.rng_nonce.set(nonce + 1);
-
-                // Generate 5 random dice (values 1-6)
-                let dice_values = roll_dice(5, seed_str.clone(), String::new())
-                    .expect("Failed to generate dice. Game cannot start with predictable dice.");
+                if game.rules.stake > Amount::ZERO {
+                    self.lock_stake(origin, game.game_id, game.rules.stake);
+                }
 
-                let player_dice = PlayerDice::from_bytes(&dice_values)
-                    .expect("Failed to parse dice values");
+                // Commit to a private seed; dice are rolled once the round seed is fixed
+                self.commit_round_seed(game.round);
+            }
 
-                // Generate salt for commitment - CRITICAL for security
-                let salt = generate_random_salt(seed_str, String::new())
-                    .expect("Failed to generate salt. Cannot proceed without secure randomness.");
+            LiarsDiceMessage::GameEvent { event } => {
+                self.assert_user_chain(chain_type);
+                // Only our game chain sends these - an update from anywhere else could be forged
+                if *self.state.user_game_chain.get() != Some(origin) {
+                    log::info!("Ignoring game event from {:?}, which is not our game chain", origin);
+                    return;
+                }
+                self.apply_event(event);
+            }
 
-                // Store dice and salt privately (NEVER sent to other chains)
-                self.state.user_dice.set(Some(player_dice.clone()));
-                self.state.user_salt.set(Some(salt));
+            LiarsDiceMessage::CommitRequested { game } => {
+                self.assert_user_chain(chain_type);
+                if !self.is_current_game(game.game_id, "CommitRequested") {
+                    return;
+                }
+                self.state.channel_game_state.set(Some(game.clone()));
 
-                // Create commitment hash = SHA-256(dice_bytes || salt)
-                let dice_bytes = player_dice.to_bytes();
-                let commitment_hash = create_commitment(&dice_bytes, &salt);
-                let dice_commitment = DiceCommitment::new(commitment_hash);
+                let chain_id = self.runtime.chain_id();
+                if game.get_player_by_chain(&chain_id).is_none_or(|p| p.eliminated) {
+                    return;
+                }
+                // A seed drawn for this round whose dice are not rolled yet may still be in flight -
+                // resend its commitment rather than drawing a new one the game chain would not match
+                match (*self.state.user_salt.get(), self.state.user_dice.get().is_none()) {
+                    (Some(seed), true) => self.send_commitment(&seed),
+                    _ => self.commit_round_seed(game.round),
+                }
+                log::info!("Re-sent dice commitment for round {}", game.round);
+            }
 
-                log::info!("Generated dice for user {:?}, sending commitment to game chain", chain_id);
+            LiarsDiceMessage::EntropyRequested { game } => {
+                self.assert_user_chain(chain_type);
+                if !self.is_current_game(game.game_id, "EntropyRequested") {
+                    return;
+                }
+                self.state.channel_game_state.set(Some(game.clone()));
 
-                // Send commitment to game chain (only hash, not actual dice!)
-                if let Some(game_chain) = self.state.user_game_chain.get().as_ref() {
+                // Every seat is bound to its share now - disclosing ours cannot be played against us
+                let chain_id = self.runtime.chain_id();
+                let in_round = game.get_player_by_chain(&chain_id).is_some_and(|p| !p.eliminated);
+                if let (Some(seed), Some((game_chain, game_id)), true) =
+                    (*self.state.user_salt.get(), self.user_game(), in_round)
+                {
+                    let entropy = entropy_share(&seed);
                     self.message_manager(
-                        *game_chain,
-                        LiarsDiceMessage::CommitDice {
-                            player_chain: chain_id,
-                            commitment: dice_commitment,
-                        },
+                        game_chain,
+                        LiarsDiceMessage::ShareEntropy { game_id, player_chain: chain_id, entropy },
                     );
+                    log::info!("Shared entropy for round {}", game.round);
+                }
+            }
+
+            LiarsDiceMessage::RoundSeedRevealed { game } => {
+                self.assert_user_chain(chain_type);
+                if !self.is_current_game(game.game_id, "RoundSeedRevealed") {
+                    return;
+                }
+                log::info!("Round seed fixed for round {} - rolling dice", game.round);
+                self.state.channel_game_state.set(Some(game.clone()));
+
+                let chain_id = self.runtime.chain_id();
+                let my_dice_count = game.players.iter()
+                    .find(|p| p.chain_id == Some(chain_id))
+                    .map(|p| p.dice_count)
+                    .unwrap_or(0);
+
+                match (*self.state.user_salt.get(), game.round_seed) {
+                    (Some(player_seed), Some(round_seed)) if my_dice_count > 0 => {
+                        // Dice = f(player seed, round seed) - the game chain re-derives them at reveal
+                        let dice_values =
+                            roll_dice_from_seeds(my_dice_count, game.rules.faces, &player_seed, &round_seed);
+                        let player_dice = PlayerDice::from_bytes(&dice_values)
+                            .expect("Failed to parse dice values");
+
+                        // Store dice privately (NEVER sent to other chains until reveal)
+                        self.state.user_dice.set(Some(player_dice));
+                        log::info!("Rolled {} dice for user {:?}", my_dice_count, chain_id);
+                    }
+                    _ => {
+                        log::info!("No dice to roll for user {:?} this round", chain_id);
+                    }
                 }
             }
 
             LiarsDiceMessage::BidMade { game, bidder, bid } => {
                 self.assert_user_chain(chain_type);
+                if !self.is_current_game(game.game_id, "BidMade") {
+                    return;
+                }
                 log::info!("Bid made by {:?}: {} x {}", bidder, bid.quantity, bid.face.value());
                 self.state.channel_game_state.set(Some(game));
             }
 
-            LiarsDiceMessage::LiarCalled { game, caller } => {
+            LiarsDiceMessage::LiarCalled { game, caller } |
+            LiarsDiceMessage::ExactCalled { game, caller } => {
                 self.assert_user_chain(chain_type);
-                log::info!("Liar called by {:?}", caller);
+                if !self.is_current_game(game.game_id, "LiarCalled") {
+                    return;
+                }
+                log::info!("Bid challenged by {:?} ({:?})", caller, game.challenge);
                 self.state.channel_game_state.set(Some(game));
 
                 // ✅ FIX Bug #10: AUTO-REVEAL using stored dice and salt
@@ -484,11 +851,12 @@ impl Contract for LiarsDiceContract {
                     self.state.user_dice.get().clone(),
                     self.state.user_salt.get().clone()
                 ) {
-                    if let Some(game_chain) = self.state.user_game_chain.get().as_ref() {
+                    if let Some((game_chain, game_id)) = self.user_game() {
                         let reveal = DiceReveal::new(dice.clone(), salt);
                         self.message_manager(
-                            *game_chain,
+                            game_chain,
                             LiarsDiceMessage::RevealDice {
+                                game_id,
                                 player_chain: chain_id,
                                 reveal,
                             },
@@ -503,648 +871,1126 @@ impl Contract for LiarsDiceContract {
             LiarsDiceMessage::RoundResult {
                 game,
                 loser,
+                gainer,
                 actual_count,
                 bid_was_valid: _,
             } => {
                 self.assert_user_chain(chain_type);
-                log::info!("Round result: loser {:?}, actual count: {}", loser, actual_count);
+                if !self.is_current_game(game.game_id, "RoundResult") {
+                    return;
+                }
+                log::info!("Round result: loser {:?}, gainer {:?}, actual count: {}", loser, gainer, actual_count);
                 self.state.channel_game_state.set(Some(game.clone()));
 
-                // ✅ FIX Bug #11: Auto-generate new dice for next round if phase is Committing
+                // ✅ FIX Bug #11: Auto-commit a new dice seed for next round if phase is Committing
                 if game.phase == abi::game::GamePhase::Committing {
-                    log::info!("New round started - generating dice for round {}", game.round);
+                    log::info!("New round started - committing dice seed for round {}", game.round);
 
+                    // Get player's actual dice count from game state
                     let chain_id = self.runtime.chain_id();
-                    let timestamp = self.runtime.system_time();
-                    let nonce = *self.state.rng_nonce.get();
-                    let round_seed = format!("{:?}_{}_{}_round_{}", chain_id, timestamp.micros(), nonce, game.round);
-                    self.state.rng_nonce.set(nonce + 1);
-
-                    // Get player's actual dice count from game state (not hardcoded 5)
                     let my_dice_count = game.players.iter()
                         .find(|p| p.chain_id == Some(chain_id))
                         .map(|p| p.dice_count)
                         .unwrap_or(0);
 
-                    // Skip dice generation if player is eliminated
+                    // Skip seed generation if player is eliminated
                     if my_dice_count == 0 {
                         log::info!("Player {:?} eliminated, skipping dice generation", chain_id);
                         return;
                     }
 
-                    // Generate dice matching player's current count
-                    let dice_values = roll_dice(my_dice_count, round_seed.clone(), String::new())
-                        .expect("Failed to generate dice for new round");
-
-                    let player_dice = PlayerDice::from_bytes(&dice_values)
-                        .expect("Failed to parse dice values");
-
-                    // Generate new salt for this round
-                    let salt = generate_random_salt(round_seed, String::new())
-                        .expect("Failed to generate salt for new round");
-
-                    // Store dice and salt privately
-                    self.state.user_dice.set(Some(player_dice.clone()));
-                    self.state.user_salt.set(Some(salt));
-
-                    // Create and send commitment
-                    let dice_bytes = player_dice.to_bytes();
-                    let commitment_hash = create_commitment(&dice_bytes, &salt);
-                    let dice_commitment = DiceCommitment::new(commitment_hash);
-
-                    log::info!("Generated new dice for round {}, sending commitment", game.round);
-
-                    if let Some(game_chain) = self.state.user_game_chain.get().as_ref() {
-                        self.message_manager(
-                            *game_chain,
-                            LiarsDiceMessage::CommitDice {
-                                player_chain: chain_id,
-                                commitment: dice_commitment,
-                            },
-                        );
-                    }
+                    self.commit_round_seed(game.round);
                 }
             }
 
             LiarsDiceMessage::GameResult {
                 game,
                 winner,
-                loser: _,
+                placement,
                 elo_change,
+                payout,
             } => {
                 self.assert_user_chain(chain_type);
-                let my_chain = self.runtime.chain_id();
-                let won = winner == my_chain;
+                // The game chain pays the tokens out itself; the stake is settled even if the player already left
+                let exited = self.state.exited_stakes.get(&game.game_id).await.expect("Failed to load exited stake");
+                if let Some(stake) = exited {
+                    self.state.exited_stakes.remove(&game.game_id).expect("Failed to remove exited stake");
+                    self.record_stake_result(stake, payout);
+                }
+                if !self.is_current_game(game.game_id, "GameResult") {
+                    return;
+                }
+                let won = placement == 1;
 
-                log::info!("Game over! Winner: {:?}, ELO change: {}", winner, elo_change);
+                log::info!("Game over! Winner: {:?}, placed {}, ELO change: {}", winner, placement, elo_change);
 
+                let stake = std::mem::take(self.state.user_stake.get_mut());
+                let chain_id = self.runtime.chain_id();
+                let cheated = game.get_player_by_chain(&chain_id).is_some_and(|p| p.result == GameResult::Cheater);
                 if let Some(ref mut profile) = *self.state.user_profile.get_mut() {
-                    if won {
-                        profile.elo = (profile.elo as i32 + elo_change) as u32;
-                    } else {
-                        profile.elo = (profile.elo as i32 - elo_change.abs()).max(100) as u32;
-                    }
+                    profile.elo = (profile.elo as i32 + elo_change).max(100) as u32;
                     profile.set_status(UserStatus::Idle);
                     profile.stats.record_game(won, game.round as u64);
+                    if cheated {
+                        profile.cheat_strikes += 1;
+                    }
                 }
+                self.record_stake_result(stake, payout);
 
                 // Clean up
                 self.state.user_game_chain.set(None);
+                self.state.user_game_id.set(None);
                 self.state.user_dice.set(None);
                 self.state.user_salt.set(None);
                 self.state.channel_game_state.set(None);
+                self.state.last_rejection.set(None);
             }
 
-            LiarsDiceMessage::LobbyInfo { lobby_chain } => {
+            LiarsDiceMessage::MatchmakingBanned { banned_until } => {
                 self.assert_user_chain(chain_type);
-                log::info!("Received lobby chain info: {:?}", lobby_chain);
-                self.state.lobby_chain.set(Some(lobby_chain));
-
-                // Subscribe to lobby chain events (following microcard pattern)
-                let app_id = self.runtime.application_id().forget_abi();
-                self.runtime.subscribe_to_events(lobby_chain, app_id, LIARS_DICE_STREAM_NAME.into());
-                log::info!("User {:?} subscribed to lobby chain {:?} for event updates",
-                    self.runtime.chain_id(), lobby_chain);
+                log::info!("Banned from matchmaking until {:?}", banned_until);
+                self.state.matchmaking_banned_until.set(Some(banned_until));
+                if let Some(ref mut profile) = *self.state.user_profile.get_mut() {
+                    if profile.status == UserStatus::FindingMatch {
+                        profile.set_status(UserStatus::Idle);
+                    }
+                }
             }
 
-            LiarsDiceMessage::ProfileUpdated { profile: _ } |
-            LiarsDiceMessage::RevealRequired { deadline: _ } => {
-                // Handle other user chain messages
+            LiarsDiceMessage::StakeReturned { game_id, amount } => {
                 self.assert_user_chain(chain_type);
+                log::info!("Stake of {} for game {} returned", amount, game_id);
+                if *self.state.user_game_id.get() == Some(game_id) {
+                    self.state.user_stake.set(Amount::ZERO);
+                }
+                self.state.exited_stakes.remove(&game_id).expect("Failed to remove exited stake");
             }
 
-            // ============================================
-            // LOBBY CHAIN MESSAGES
-            // ============================================
-            LiarsDiceMessage::FindMatch { player } => {
-                self.assert_lobby_chain(chain_type);
-                log::info!("Player {:?} looking for match", player.chain_id);
-
-                // Add to queue (not async in QueueView)
-                self.state.matchmaking_queue.push_back(player.clone());
-                let count = self.state.queue_count.get_mut();
-                *count += 1;
+            LiarsDiceMessage::RematchOffered { offer } => {
+                self.assert_user_chain(chain_type);
+                // Only relevant between games
+                if self.state.user_game_id.get().is_some() {
+                    return;
+                }
+                log::info!("Rematch of game {}: {} players agreed", offer.game_id, offer.accepted.len());
+                self.state.user_rematch.set(Some(offer));
+            }
 
-                // Emit queue update
-                self.runtime.emit(
-                    LIARS_DICE_STREAM_NAME.into(),
-                    &LiarsDiceEvent::QueueUpdate { players_in_queue: *count },
-                );
+            LiarsDiceMessage::ActionRejected {
+                game_id,
+                action,
+                error,
+            } => {
+                self.assert_user_chain(chain_type);
+                log::info!("{} rejected by game chain: {}", action, error);
+                if action == "RequestRematch" {
+                    self.state.user_rematch.set(None);
+                }
 
-                // Try to match players
-                self.try_match_players().await;
+                self.state.last_rejection.set(Some(RejectedAction {
+                    game_id,
+                    action,
+                    error,
+                    rejected_at: Some(self.runtime.system_time()),
+                }));
             }
 
-            LiarsDiceMessage::CancelMatch { player_chain } => {
-                self.assert_lobby_chain(chain_type);
-                log::info!("Player {:?} cancelling match", player_chain);
+            LiarsDiceMessage::LobbyInfo { lobby_chain } => {
+                self.assert_user_chain(chain_type);
+                log::info!("Received lobby chain info: {:?}", lobby_chain);
+                self.state.lobby_chain.set(Some(lobby_chain));
 
-                // ✅ FIX: Optimized queue removal - collect all players first to minimize async calls
-                // QueueView doesn't support direct removal, so we need to:
-                // 1. Pop all items into a Vec (single pass)
-                // 2. Filter and re-add all items except the cancelled player
-                let queue_count = *self.state.queue_count.get();
-
-                // Collect all players from queue in one pass
-                let all_players: Vec<QueuedPlayer> = {
-                    let mut players = Vec::with_capacity(queue_count as usize);
-                    for _ in 0..queue_count {
-                        if let Ok(Some(player)) = self.state.matchmaking_queue.front().await {
-                            self.state.matchmaking_queue.delete_front();
-                            players.push(player);
-                        }
-                    }
-                    players
-                };
+                // Subscribe to lobby chain events (following microcard pattern)
+                let app_id = self.runtime.application_id().forget_abi();
+                self.runtime.subscribe_to_events(lobby_chain, app_id, LIARS_DICE_STREAM_NAME.into());
+                log::info!("User {:?} subscribed to lobby chain {:?} for event updates",
+                    self.runtime.chain_id(), lobby_chain);
+            }
 
-                // Filter and re-add in one pass
-                let mut removed = false;
-                for player in all_players {
-                    if player.chain_id != player_chain {
-                        self.state.matchmaking_queue.push_back(player);
-                    } else {
-                        removed = true;
-                        log::info!("Removed player {:?} from queue", player_chain);
-                    }
-                }
+            LiarsDiceMessage::RoomUpdate { room } => {
+                self.assert_user_chain(chain_type);
+                log::info!("Room {}: {} of {} seats taken", room.room_id, room.player_count, room.max_players);
+                self.state.user_room.set(Some(room));
+                self.state.last_room_rejection.set(None);
+            }
 
-                // Update queue count
-                if removed {
-                    let count = self.state.queue_count.get_mut();
-                    *count = count.saturating_sub(1);
+            LiarsDiceMessage::RoomLeft { room_id } => {
+                self.assert_user_chain(chain_type);
+                log::info!("Left room {}", room_id);
+                if self.state.user_room.get().as_ref().is_some_and(|room| room.room_id == room_id) {
+                    self.state.user_room.set(None);
+                }
+            }
 
-                    // Emit queue update event
-                    self.runtime.emit(
-                        LIARS_DICE_STREAM_NAME.into(),
-                        &LiarsDiceEvent::QueueUpdate { players_in_queue: *count },
-                    );
+            LiarsDiceMessage::ChallengeUpdate { challenge } => {
+                self.assert_user_chain(chain_type);
+                let challenge_id = challenge.challenge_id;
+                log::info!("Challenge {} from {}: {:?}", challenge_id, challenge.challenger.name, challenge.status);
+                if challenge.status == ChallengeStatus::Pending {
+                    self.state.user_challenges.insert(&challenge_id, challenge).expect("Failed to store challenge");
+                } else {
+                    self.state.user_challenges.remove(&challenge_id).expect("Failed to remove challenge");
                 }
             }
 
-            LiarsDiceMessage::GameEnded {
-                game_chain,
-                winner: _,
-                loser: _,
+            LiarsDiceMessage::RoomActionRejected {
+                room_id,
+                action,
+                error,
             } => {
-                self.assert_lobby_chain(chain_type);
-                log::info!("Game ended on {:?}", game_chain);
-
-                // Return game chain to pool
-                self.state.available_game_chains.push_back(game_chain);
-                self.state.active_game_chains.remove(&game_chain).expect("Failed to remove game chain");
+                self.assert_user_chain(chain_type);
+                log::info!("{} rejected by lobby: {}", action, error);
+
+                self.state.last_room_rejection.set(Some(RejectedRoomAction {
+                    room_id,
+                    action,
+                    error,
+                    rejected_at: Some(self.runtime.system_time()),
+                }));
             }
 
-            LiarsDiceMessage::RegisterGameChain { chain_id } => {
-                self.assert_lobby_chain(chain_type);
-                log::info!("Registering game chain: {:?}", chain_id);
-                self.state.available_game_chains.push_back(chain_id);
+            LiarsDiceMessage::ProfileUpdated { profile: _ } |
+            LiarsDiceMessage::RevealRequired { deadline: _ } => {
+                // Handle other user chain messages
+                self.assert_user_chain(chain_type);
             }
 
             // ============================================
-            // GAME CHAIN MESSAGES
+            // LOBBY CHAIN MESSAGES
             // ============================================
-            LiarsDiceMessage::AssignMatch {
-                game_id,
-                player1,
-                player2,
-            } => {
-                self.assert_game_chain(chain_type);
-                log::info!("Assigned match {} with players", game_id);
-
-                // Create new game
-                let mut game = LiarsDiceGame::new(game_id);
+            LiarsDiceMessage::FindMatch { mut player } => {
+                self.assert_lobby_chain(chain_type);
+                log::info!("Player {:?} looking for match", player.chain_id);
 
-                // Add players (preserving ELO from matchmaking)
-                let gp1 = GamePlayer::new(player1.chain_id, player1.owner, player1.name.clone(), player1.elo);
-                let gp2 = GamePlayer::new(player2.chain_id, player2.owner, player2.name.clone(), player2.elo);
-                game.add_player(gp1);
-                game.add_player(gp2);
+                // One entry per player: a repeated FindMatch keeps the original place in line
+                let player_chain = player.chain_id;
+                let queued =
+                    self.state.matchmaking_queue.contains_key(&player_chain).await.expect("Failed to check queue");
+                if self.check_ban(player_chain).await.is_some() {
+                    log::info!("Player {:?} is banned, not queueing", player_chain);
+                } else if queued {
+                    log::info!("Player {:?} is already queued", player_chain);
+                } else if self.state.player_rooms.contains_key(&player_chain).await.expect("Failed to check rooms") {
+                    log::info!("Player {:?} is in a room, not queueing", player_chain);
+                } else {
+                    let position = *self.state.next_queue_position.get();
+                    self.state.next_queue_position.set(position + 1);
+                    player.queue_position = position;
+                    self.state.queue_order.insert(&position, player_chain).expect("Failed to order queue");
+                    self.state.matchmaking_queue.insert(&player_chain, player).expect("Failed to queue player");
+                    self.announce_queue().await;
+                }
 
-                // Start game
-                game.start_game(self.runtime.system_time());
+                // Try to match players
+                self.try_match_players().await;
 
-                // Store game state
-                self.state.current_game.set(Some(game.clone()));
-                self.state.game_chain_available.set(false);
+                // Lobby traffic doubles as the clock for stalled games
+                self.sweep_timeouts().await;
+            }
 
-                // Notify players
-                self.message_manager(player1.chain_id, LiarsDiceMessage::GameStarted { game: game.clone() });
-                self.message_manager(player2.chain_id, LiarsDiceMessage::GameStarted { game: game.clone() });
+            LiarsDiceMessage::CancelMatch { player_chain } => {
+                self.assert_lobby_chain(chain_type);
+                log::info!("Player {:?} cancelling match", player_chain);
 
-                // Emit game state event
-                self.runtime.emit(
-                    LIARS_DICE_STREAM_NAME.into(),
-                    &LiarsDiceEvent::GameState { game },
-                );
+                if self.dequeue(player_chain).await.is_some() {
+                    log::info!("Removed player {:?} from queue", player_chain);
+                    self.announce_queue().await;
+                }
             }
 
-            LiarsDiceMessage::CommitDice {
-                player_chain,
-                commitment,
+            LiarsDiceMessage::GameEnded {
+                game_chain,
+                game_id,
+                winner: _,
+                cheaters,
             } => {
-                self.assert_game_chain(chain_type);
-                log::info!("Received commitment from {:?}", player_chain);
-
-                if let Some(ref mut game) = *self.state.current_game.get_mut() {
-                    // ✅ FIX: Add phase validation
-                    if game.phase != abi::game::GamePhase::Committing {
-                        log::error!(
-                            "Cannot commit dice in {:?} phase - must be in Committing phase",
-                            game.phase
-                        );
+                self.assert_lobby_chain(chain_type);
+                log::info!("Game {} ended on {:?}", game_id, game_chain);
+
+                // Only the hosting chain can end its games
+                let room_id = match self.state.hosted_games.get(&game_id).await.expect("Failed to load hosted game") {
+                    Some(hosted) if hosted.game_chain == Some(origin) => {
+                        self.state.hosted_games.remove(&game_id).expect("Failed to remove hosted game");
+                        self.unseat_players(&hosted).await;
+                        hosted.room_id
+                    }
+                    _ => {
+                        log::error!("GameEnded for game {} from {:?}, which is not hosting it", game_id, origin);
                         return;
                     }
+                };
 
-                    if let Some(player) = game.get_player_mut_by_chain(&player_chain) {
-                        player.set_commitment(commitment);
+                // Strike the cheaters; repeat offenders are banned from matchmaking for a while
+                let now = self.runtime.system_time();
+                for cheater in cheaters {
+                    let mut record = self.state.cheat_records.get(&cheater).await
+                        .expect("Failed to load cheat record")
+                        .unwrap_or_default();
+                    record.record_strike(now);
+                    log::info!("Cheat strike {} for {:?} in game {}", record.strikes, cheater, game_id);
+                    if let Some(banned_until) = record.banned_until.filter(|_| record.is_banned(now)) {
+                        self.push_ban(cheater, banned_until).await;
                     }
+                    self.state.cheat_records.insert(&cheater, record).expect("Failed to store cheat record");
+                }
 
-                    // Check if all committed
-                    if game.all_committed() {
-                        game.start_bidding();
-                        log::info!("All players committed, starting bidding phase");
-
-                        // Emit game state
-                        self.runtime.emit(
-                            LIARS_DICE_STREAM_NAME.into(),
-                            &LiarsDiceEvent::GameState { game: game.clone() },
-                        );
+                // A room game frees the room for the next one
+                if let Some(room_id) = room_id {
+                    if let Ok(Some(mut room)) = self.state.rooms.get(&room_id).await {
+                        room.game_ended(now);
+                        self.publish_room(room);
                     }
                 }
-            }
-
-            LiarsDiceMessage::MakeBid { player_chain, bid } => {
-                self.assert_game_chain(chain_type);
-                log::info!("Bid from {:?}: {} x {}", player_chain, bid.quantity, bid.face.value());
 
-                // Collect data while holding mutable borrow, then release it
-                let send_data = {
-                    if let Some(ref mut game) = *self.state.current_game.get_mut() {
-                        // ✅ FIX: Add phase validation
-                        if game.phase != abi::game::GamePhase::Bidding {
-                            log::error!(
-                                "Cannot make bid in {:?} phase - must be in Bidding phase",
-                                game.phase
-                            );
-                            None
-                        } else if game.make_bid(bid.clone()) {
-                            let player_chains: Vec<ChainId> = game.players
-                                .iter()
-                                .filter_map(|p| p.chain_id)
-                                .collect();
-                            Some((game.clone(), player_chains, game.game_id))
+                // Free the game's slot (or let a drained chain go with its last game)
+                match self.state.game_chains.get(&origin).await.expect("Failed to load game chain") {
+                    Some(mut info) => {
+                        info.end_game(now);
+                        info.heartbeat(now);
+                        if info.draining && !info.in_use() {
+                            log::info!("Drained game chain {:?} removed from the pool", origin);
+                            self.state.game_chains.remove(&origin).expect("Failed to remove game chain");
                         } else {
-                            None
+                            self.state.game_chains.insert(&origin, info).expect("Failed to update game chain");
                         }
-                    } else {
-                        None
                     }
-                };
+                    None => log::error!("GameEnded from {:?}, which is not in the pool", origin),
+                }
 
-                // Now send messages with collected data
-                if let Some((game_state, player_chains, game_id)) = send_data {
-                    for chain_id in player_chains {
-                        self.message_manager(
-                            chain_id,
-                            LiarsDiceMessage::BidMade {
-                                game: game_state.clone(),
-                                bidder: player_chain,
-                                bid: bid.clone(),
-                            },
-                        );
-                    }
+                // A slot came free - seat anyone waiting for one
+                self.try_match_players().await;
+            }
 
-                    self.runtime.emit(
-                        LIARS_DICE_STREAM_NAME.into(),
-                        &LiarsDiceEvent::BidUpdate { game_id, bid },
-                    );
+            LiarsDiceMessage::GameDeadline { game_id, deadline } => {
+                self.assert_lobby_chain(chain_type);
+                // Reported by the hosting game chain itself; ignore anyone else
+                match self.state.hosted_games.get(&game_id).await {
+                    Ok(Some(mut hosted)) if hosted.game_chain == Some(origin) => {
+                        hosted.deadline = deadline;
+                        self.state.hosted_games.insert(&game_id, hosted).expect("Failed to update hosted game");
+                    }
+                    _ => return,
+                }
+                if let Ok(Some(mut info)) = self.state.game_chains.get(&origin).await {
+                    info.heartbeat(self.runtime.system_time());
+                    self.state.game_chains.insert(&origin, info).expect("Failed to update game chain");
                 }
             }
 
-            LiarsDiceMessage::CallLiar { player_chain } => {
-                self.assert_game_chain(chain_type);
-                log::info!("Liar called by {:?}", player_chain);
-
-                let timestamp = self.runtime.system_time();
-
-                // Collect data while holding mutable borrow
-                let send_data = {
-                    if let Some(ref mut game) = *self.state.current_game.get_mut() {
-                        // ✅ FIX: Add phase validation
-                        if game.phase != abi::game::GamePhase::Bidding {
-                            log::error!(
-                                "Cannot call liar in {:?} phase - must be in Bidding phase",
-                                game.phase
-                            );
-                            None
-                        } else if game.call_liar(player_chain, timestamp) {
-                            let player_chains: Vec<ChainId> = game.players
-                                .iter()
-                                .filter_map(|p| p.chain_id)
-                                .collect();
-                            Some((game.clone(), player_chains, game.game_id))
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
+            LiarsDiceMessage::PoolHeartbeat => {
+                self.assert_lobby_chain(chain_type);
+                let recovered = match self.state.game_chains.get(&origin).await {
+                    Ok(Some(mut info)) => {
+                        let was_inactive = info.chain_status == ActivityStatus::Inactive;
+                        info.heartbeat(self.runtime.system_time());
+                        self.state.game_chains.insert(&origin, info).expect("Failed to update game chain");
+                        was_inactive
+                    }
+                    _ => {
+                        log::error!("Heartbeat from {:?}, which is not in the pool", origin);
+                        false
                     }
                 };
+                if recovered {
+                    log::info!("Game chain {:?} is healthy again", origin);
+                    self.try_match_players().await;
+                }
+            }
 
-                // Now send messages
-                if let Some((game_state, player_chains, game_id)) = send_data {
-                    for chain_id in player_chains {
-                        self.message_manager(
-                            chain_id,
-                            LiarsDiceMessage::LiarCalled {
-                                game: game_state.clone(),
-                                caller: player_chain,
-                            },
-                        );
-                    }
+            LiarsDiceMessage::CreateRoom {
+                host,
+                name,
+                max_players,
+                password_hash,
+            } => {
+                self.assert_lobby_chain(chain_type);
+                if host.chain_id != origin {
+                    log::error!("CreateRoom for {:?} sent by {:?}", host.chain_id, origin);
+                    return;
+                }
+                if !RoomInfo::is_valid_seat_count(max_players) {
+                    self.reject_room_action(origin, None, "CreateRoom", RoomError::InvalidSeatCount);
+                    return;
+                }
+                if self.state.player_rooms.contains_key(&origin).await.expect("Failed to check rooms") {
+                    self.reject_room_action(origin, None, "CreateRoom", RoomError::AlreadyInRoom);
+                    return;
+                }
+                if self.check_ban(origin).await.is_some() {
+                    self.reject_room_action(origin, None, "CreateRoom", RoomError::PlayerBanned);
+                    return;
+                }
 
-                    self.runtime.emit(
-                        LIARS_DICE_STREAM_NAME.into(),
-                        &LiarsDiceEvent::LiarCalledEvent {
-                            game_id,
-                            caller: player_chain,
-                        },
-                    );
+                let room_id = *self.state.next_room_id.get();
+                self.state.next_room_id.set(room_id + 1);
+                let room_type = match password_hash {
+                    Some((password_hash, salt)) => RoomType::Private { password_hash, salt },
+                    None => RoomType::Public,
+                };
+                let room = RoomInfo::new(room_id, name, room_type, host, max_players, self.runtime.system_time());
+                log::info!("Room {} opened by {:?}", room_id, origin);
+
+                // A player waits in a room or in the queue, not both
+                if self.dequeue(origin).await.is_some() {
+                    self.announce_queue().await;
                 }
+                self.state.player_rooms.insert(&origin, room_id).expect("Failed to track room player");
+                self.publish_room(room);
             }
 
-            LiarsDiceMessage::RevealDice {
-                player_chain,
-                reveal,
+            LiarsDiceMessage::JoinRoom {
+                room_id,
+                player,
+                password_hash,
             } => {
-                self.assert_game_chain(chain_type);
-                log::info!("Reveal from {:?}", player_chain);
+                self.assert_lobby_chain(chain_type);
+                if player.chain_id != origin {
+                    log::error!("JoinRoom for {:?} sent by {:?}", player.chain_id, origin);
+                    return;
+                }
+                if self.state.player_rooms.contains_key(&origin).await.expect("Failed to check rooms") {
+                    self.reject_room_action(origin, Some(room_id), "JoinRoom", RoomError::AlreadyInRoom);
+                    return;
+                }
+                if self.check_ban(origin).await.is_some() {
+                    self.reject_room_action(origin, Some(room_id), "JoinRoom", RoomError::PlayerBanned);
+                    return;
+                }
+                let Some(mut room) = self.load_room(origin, room_id, "JoinRoom").await else {
+                    return;
+                };
+                let joined = room
+                    .check_password(password_hash.as_deref())
+                    .and_then(|()| room.add_player(player, self.runtime.system_time()));
+                if let Err(error) = joined {
+                    self.reject_room_action(origin, Some(room_id), "JoinRoom", error);
+                    return;
+                }
+                log::info!("Player {:?} joined room {}", origin, room_id);
 
-                let should_resolve = {
-                    let game = self.state.current_game.get_mut();
-                    if let Some(ref mut game) = *game {
-                        // ✅ FIX: Add phase validation
-                        if game.phase != abi::game::GamePhase::Revealing {
-                            log::error!(
-                                "Cannot reveal dice in {:?} phase - must be in Revealing phase",
-                                game.phase
-                            );
-                            return;
-                        }
+                if self.dequeue(origin).await.is_some() {
+                    self.announce_queue().await;
+                }
+                self.state.player_rooms.insert(&origin, room_id).expect("Failed to track room player");
+                self.publish_room(room);
+            }
 
-                        if let Some(player) = game.get_player_mut_by_chain(&player_chain) {
-                            // Verify commitment
-                            if let Some(ref mut commitment) = player.commitment {
-                                let dice_bytes = reveal.dice.to_bytes();
-                                if verify_commitment(&dice_bytes, &reveal.salt, &commitment.hash) {
-                                    commitment.mark_revealed();
-                                    player.revealed_dice = Some(reveal.dice.clone());
-                                    player.dice_count = reveal.dice.count;
-                                    log::info!("Valid reveal from {:?}", player_chain);
-
-                                    self.runtime.emit(
-                                        LIARS_DICE_STREAM_NAME.into(),
-                                        &LiarsDiceEvent::DiceRevealed {
-                                            game_id: game.game_id,
-                                            player: player_chain,
-                                            dice: reveal.dice,
-                                        },
-                                    );
-                                } else {
-                                    // CHEATER DETECTED - mark commitment so all_revealed() returns true
-                                    log::error!("CHEATER DETECTED: {:?} - invalid reveal!", player_chain);
-                                    commitment.mark_cheater();
-                                    player.result = abi::game::GameResult::Cheater;
-                                    player.eliminated = true;
-                                    player.dice_count = 0;
-                                }
-                            }
-                        }
-                        game.all_revealed()
-                    } else {
-                        false
+            LiarsDiceMessage::LeaveRoom { room_id } => {
+                self.assert_lobby_chain(chain_type);
+                if let Some(room) = self.load_room(origin, room_id, "LeaveRoom").await {
+                    if let Err(error) = self.remove_from_room(room, origin).await {
+                        self.reject_room_action(origin, Some(room_id), "LeaveRoom", error);
                     }
+                }
+            }
+
+            LiarsDiceMessage::KickFromRoom { room_id, player_chain } => {
+                self.assert_lobby_chain(chain_type);
+                let Some(room) = self.load_room(origin, room_id, "KickFromRoom").await else {
+                    return;
+                };
+                let allowed = if !room.is_host(origin) {
+                    Err(RoomError::NotHost)
+                } else if room.current_game.is_some() {
+                    Err(RoomError::GameInProgress)
+                } else {
+                    Ok(())
+                };
+                let kicked = match allowed {
+                    Ok(()) => self.remove_from_room(room, player_chain).await,
+                    Err(error) => Err(error),
                 };
+                if let Err(error) = kicked {
+                    self.reject_room_action(origin, Some(room_id), "KickFromRoom", error);
+                }
+            }
 
-                // Check if all revealed
-                if should_resolve {
-                    self.resolve_round().await;
+            LiarsDiceMessage::SetRoomSeats { room_id, max_players } => {
+                self.assert_lobby_chain(chain_type);
+                let Some(mut room) = self.load_room(origin, room_id, "SetRoomSeats").await else {
+                    return;
+                };
+                let resized = if room.is_host(origin) {
+                    room.set_max_players(max_players, self.runtime.system_time())
+                } else {
+                    Err(RoomError::NotHost)
+                };
+                match resized {
+                    Ok(()) => self.publish_room(room),
+                    Err(error) => self.reject_room_action(origin, Some(room_id), "SetRoomSeats", error),
                 }
             }
 
-            LiarsDiceMessage::PlayerForfeit { player_chain } => {
-                self.assert_game_chain(chain_type);
-                log::info!("Player {:?} forfeited", player_chain);
+            LiarsDiceMessage::SetRoomSpectatorDelay { room_id, delay } => {
+                self.assert_lobby_chain(chain_type);
+                let Some(mut room) = self.load_room(origin, room_id, "SetRoomSpectatorDelay").await else {
+                    return;
+                };
+                if !room.is_host(origin) {
+                    self.reject_room_action(origin, Some(room_id), "SetRoomSpectatorDelay", RoomError::NotHost);
+                    return;
+                }
+                // Applies from the room's next game
+                room.spectator_delay = delay;
+                room.last_update = Some(self.runtime.system_time());
+                self.publish_room(room);
+            }
 
-                // Eliminate the forfeiting player
-                let should_end = {
-                    if let Some(ref mut game) = *self.state.current_game.get_mut() {
-                        if let Some(player) = game.get_player_mut_by_chain(&player_chain) {
-                            player.eliminated = true;
-                            player.dice_count = 0;
-                            player.result = abi::game::GameResult::Lost;
-                            log::info!("Player {:?} eliminated due to forfeit", player_chain);
-                        }
+            LiarsDiceMessage::StartRoomGame { room_id } => {
+                self.assert_lobby_chain(chain_type);
+                let Some(mut room) = self.load_room(origin, room_id, "StartRoomGame").await else {
+                    return;
+                };
+                // Players banned since they joined cannot be seated
+                let mut banned = false;
+                for player in &room.players {
+                    banned |= self.check_ban(player.chain_id).await.is_some();
+                }
+                let game_chain = if !room.is_host(origin) {
+                    Err(RoomError::NotHost)
+                } else if room.current_game.is_some() {
+                    Err(RoomError::GameInProgress)
+                } else if (room.player_count as usize) < LiarsDiceGame::MIN_PLAYERS {
+                    Err(RoomError::NotEnoughPlayers)
+                } else if banned {
+                    Err(RoomError::PlayerBanned)
+                } else {
+                    self.free_game_chain().await.ok_or(RoomError::NoFreeGameChain)
+                };
+                let game_chain = match game_chain {
+                    Ok(game_chain) => game_chain,
+                    Err(error) => {
+                        self.reject_room_action(origin, Some(room_id), "StartRoomGame", error);
+                        return;
+                    }
+                };
 
-                        // Update total dice count
-                        game.total_dice = game.players.iter().map(|p| p.dice_count).sum();
+                // The room's spectator delay and stake tier override the lobby's
+                let rules = GameRules {
+                    spectator_delay: room.spectator_delay,
+                    stake: room.stake_tier.stake(),
+                    ..self.state.game_rules.get().clone()
+                };
+                let game_id = self.start_match(game_chain, room.players.clone(), rules, Some(room_id)).await;
+                room.assign_game_chain(game_chain, game_id, self.runtime.system_time());
+                self.publish_room(room);
+            }
 
-                        // Check if game should end
-                        let active_count = game.players.iter().filter(|p| !p.eliminated).count();
-                        active_count == 1
-                    } else {
-                        false
+            LiarsDiceMessage::ChallengePlayer { challenger, target_chain } => {
+                self.assert_lobby_chain(chain_type);
+                if challenger.chain_id != origin || target_chain == origin {
+                    log::error!("Invalid challenge from {:?} to {:?}", origin, target_chain);
+                    return;
+                }
+                if self.check_ban(origin).await.is_some() {
+                    log::info!("Player {:?} is banned, not challenging {:?}", origin, target_chain);
+                    return;
+                }
+                self.expire_challenges().await;
+                if self.is_busy(origin).await {
+                    self.reject_room_action(origin, None, "ChallengePlayer", RoomError::PlayerBusy);
+                    return;
+                }
+                if self.open_challenges(origin).await >= MAX_OPEN_CHALLENGES {
+                    self.reject_room_action(origin, None, "ChallengePlayer", RoomError::TooManyChallenges);
+                    return;
+                }
+                let challenge_id = *self.state.next_challenge_id.get();
+                self.state.next_challenge_id.set(challenge_id + 1);
+                let challenge = Challenge::new(challenge_id, challenger, target_chain, self.runtime.system_time());
+                log::info!("Player {:?} challenges {:?} (challenge {})", origin, target_chain, challenge_id);
+
+                // Deliver the invitation to the target (and the id back to the challenger)
+                self.message_manager(origin, LiarsDiceMessage::ChallengeUpdate { challenge: challenge.clone() });
+                self.message_manager(target_chain, LiarsDiceMessage::ChallengeUpdate { challenge: challenge.clone() });
+                self.state.challenges.insert(&challenge_id, challenge).expect("Failed to store challenge");
+            }
+
+            LiarsDiceMessage::AcceptChallenge { challenge_id, player } => {
+                self.assert_lobby_chain(chain_type);
+                self.expire_challenges().await;
+                let challenge = self.state.challenges.get(&challenge_id).await.expect("Failed to load challenge");
+                let challenge = match challenge {
+                    Some(challenge) if challenge.target_chain == origin && player.chain_id == origin => challenge,
+                    _ => {
+                        log::error!("AcceptChallenge {} from {:?}: no such open challenge", challenge_id, origin);
+                        return;
                     }
                 };
 
-                // If only one player left, end the game
-                if should_end {
-                    let timestamp = self.runtime.system_time();
-                    let (game_data, winner_chain, loser_chain) = {
-                        let game = self.state.current_game.get_mut();
-                        if let Some(ref mut game) = *game {
-                            let winner = game.players.iter()
-                                .find(|p| !p.eliminated)
-                                .and_then(|p| p.chain_id);
-
-                            if let Some(winner_chain) = winner {
-                                game.winner = Some(winner_chain);
-                                game.phase = GamePhase::GameOver;
-                                game.ended_at = Some(timestamp);
-
-                                if let Some(winner_player) = game.get_player_mut_by_chain(&winner_chain) {
-                                    winner_player.result = abi::game::GameResult::Won;
-                                }
+                // The challenge is played at the challenger's tier, which the target's chain checked
+                if player.stake_tier != challenge.challenger.stake_tier {
+                    self.reject_room_action(origin, None, "AcceptChallenge", RoomError::WrongStakeTier);
+                    return;
+                }
 
-                                (Some(game.clone()), Some(winner_chain), player_chain)
-                            } else {
-                                (None, None, player_chain)
-                            }
-                        } else {
-                            (None, None, player_chain)
-                        }
-                    };
+                // Either side may have been banned since the challenge was issued
+                let challenger_banned = self.check_ban(challenge.challenger.chain_id).await.is_some();
+                if challenger_banned || self.check_ban(origin).await.is_some() {
+                    log::info!("Challenge {} cannot be played: a player is banned", challenge_id);
+                    self.close_challenge(challenge, ChallengeStatus::Declined);
+                    return;
+                }
 
-                    // Send game result messages
-                    if let (Some(game_state), Some(winner)) = (game_data, winner_chain) {
-                        // ✅ FIX: Get actual ELOs from players
-                        let winner_elo = game_state.players.iter()
-                            .find(|p| p.chain_id == Some(winner))
-                            .map(|p| p.elo)
-                            .unwrap_or(STARTING_ELO);
-
-                        let loser_elo = game_state.players.iter()
-                            .find(|p| p.chain_id == Some(loser_chain))
-                            .map(|p| p.elo)
-                            .unwrap_or(STARTING_ELO);
-
-                        let elo_change = calculate_elo_change(winner_elo, loser_elo, true);
-                        let player_chains: Vec<ChainId> = game_state.players
-                            .iter()
-                            .filter_map(|p| p.chain_id)
-                            .collect();
-
-                        for chain_id in player_chains {
-                            self.message_manager(
-                                chain_id,
-                                LiarsDiceMessage::GameResult {
-                                    game: game_state.clone(),
-                                    winner,
-                                    loser: loser_chain,
-                                    elo_change,
-                                },
-                            );
-                        }
+                // Neither side can be at another table; the challenge stays open until they are free
+                let challenger_busy = self.is_busy(challenge.challenger.chain_id).await;
+                if challenger_busy || self.is_busy(origin).await {
+                    self.reject_room_action(origin, None, "AcceptChallenge", RoomError::PlayerBusy);
+                    return;
+                }
 
-                        self.runtime.emit(
-                            LIARS_DICE_STREAM_NAME.into(),
-                            &LiarsDiceEvent::GameEnded {
-                                game_id: game_state.game_id,
-                                winner,
-                                loser: loser_chain,
-                            },
-                        );
+                // Challenges skip the queue: the lobby picks a game chain for the two players directly
+                let Some(game_chain) = self.free_game_chain().await else {
+                    log::info!("No free game slot for challenge {}, it stays open", challenge_id);
+                    self.reject_room_action(origin, None, "AcceptChallenge", RoomError::NoFreeGameChain);
+                    return;
+                };
+                let challenger = challenge.challenger.clone();
+                self.close_challenge(challenge, ChallengeStatus::Accepted);
+                let dequeued = [self.dequeue(challenger.chain_id).await, self.dequeue(origin).await];
+                if dequeued.iter().any(Option::is_some) {
+                    self.announce_queue().await;
+                }
+                let mut rules = self.state.game_rules.get().clone();
+                rules.stake = challenger.stake_tier.stake();
+                self.start_match(game_chain, vec![challenger, player], rules, None).await;
+            }
 
-                        // ✅ FIX: Send leaderboard update to master chain
-                        let winner_name = game_state.players.iter()
-                            .find(|p| p.chain_id == Some(winner))
-                            .map(|p| p.name.clone())
-                            .unwrap_or_else(|| "Unknown".to_string());
+            LiarsDiceMessage::DeclineChallenge { challenge_id } => {
+                self.assert_lobby_chain(chain_type);
+                // Either side can call it off
+                match self.state.challenges.get(&challenge_id).await.expect("Failed to load challenge") {
+                    Some(challenge) if challenge.involves(origin) => {
+                        self.close_challenge(challenge, ChallengeStatus::Declined);
+                    }
+                    _ => log::error!("DeclineChallenge {} from {:?}, which is not part of it", challenge_id, origin),
+                }
+            }
 
-                        let loser_name = game_state.players.iter()
-                            .find(|p| p.chain_id == Some(loser_chain))
-                            .map(|p| p.name.clone())
-                            .unwrap_or_else(|| "Unknown".to_string());
+            LiarsDiceMessage::RematchStarted { game_id, players } => {
+                self.assert_lobby_chain(chain_type);
+                // Rematches are started by pooled chains, of games this lobby matched, once
+                let issued_here = game_id.lobby_chain == Some(self.runtime.chain_id()) && game_id.rematch > 0;
+                let hosted = self.state.hosted_games.contains_key(&game_id).await.expect("Failed to check games");
+                let info = self.state.game_chains.get(&origin).await.expect("Failed to load game chain");
+                let Some(mut info) = info.filter(|_| issued_here && !hosted) else {
+                    log::error!("Rematch {} from {:?} rejected", game_id, origin);
+                    return;
+                };
 
-                        let winner_new_elo = (winner_elo as i32 + elo_change) as u32;
-                        let loser_new_elo = (loser_elo as i32 - elo_change.abs()).max(100) as u32;
+                // The game chain checked the bans and its capacity we pushed to it; track the game like any other
+                let now = self.runtime.system_time();
+                info.start_game(now);
+                info.heartbeat(now);
+                self.state.game_chains.insert(&origin, info).expect("Failed to update game chain");
+                self.seat_players(&HostedGame::new(game_id, origin, players, now));
+                log::info!("Rematch {} started on {:?}", game_id, origin);
+            }
 
-                        let master_chain = self.get_master_chain();
-                        self.message_manager(
-                            master_chain,
-                            LiarsDiceMessage::UpdateLeaderboard {
-                                winner,
-                                winner_name,
-                                winner_new_elo,
-                                loser: loser_chain,
-                                loser_name,
-                                loser_new_elo,
-                            },
-                        );
+            LiarsDiceMessage::RegisterGameChain { chain_id } => {
+                self.assert_lobby_chain(chain_type);
+                match self.state.game_chains.get(&chain_id).await.expect("Failed to load game chain") {
+                    Some(info) if info.chain_status == ActivityStatus::MaintenanceMode => {
+                        log::error!("Game chain {:?} is quarantined (maintenance mode), not re-registering", chain_id);
+                        return;
+                    }
+                    Some(mut info) => {
+                        // Re-registering a draining chain keeps it in the pool
+                        log::info!("Game chain {:?} already registered", chain_id);
+                        info.draining = false;
+                        self.push_pool_capacity(&info);
+                        self.state.game_chains.insert(&chain_id, info).expect("Failed to update game chain");
+                    }
+                    None => {
+                        log::info!("Registering game chain: {:?}", chain_id);
+                        let info = GameChainInfo::new(chain_id, self.runtime.system_time());
+                        self.push_pool_capacity(&info);
+                        self.state.game_chains.insert(&chain_id, info).expect("Failed to insert game chain");
                     }
                 }
+
+                // Players may be waiting for a free chain
+                self.try_match_players().await;
             }
 
             // ============================================
-            // MASTER CHAIN MESSAGES
+            // GAME CHAIN MESSAGES
             // ============================================
-            LiarsDiceMessage::RequestLobbyInfo { user_chain } => {
-                self.assert_master_chain(chain_type);
-                log::info!("Lobby info requested by {:?}", user_chain);
+            LiarsDiceMessage::AssignMatch {
+                game_id,
+                rules,
+                players,
+            } => {
+                self.assert_game_chain(chain_type);
+                if !self.authorize_lobby(origin, Some(game_id), "AssignMatch") {
+                    return;
+                }
+                log::info!("Assigned match {} with {} players", game_id, players.len());
+                // Game ids are issued by the lobby that matched the game, and only once
+                if game_id.lobby_chain != Some(origin)
+                    || self.state.games.contains_key(&game_id).await.expect("Failed to check games")
+                {
+                    log::error!("Match {} was not issued by {:?} or is already being played", game_id, origin);
+                    return;
+                }
 
-                // Get first lobby chain and send to user
-                let lobby_keys = self.state.lobby_chains.indices().await.expect("Failed to get lobby chains");
-                if let Some(lobby_chain) = lobby_keys.into_iter().next() {
-                    self.message_manager(
-                        user_chain,
-                        LiarsDiceMessage::LobbyInfo { lobby_chain },
-                    );
+                // Create new game under the rules chosen by the lobby
+                // Seat players in queue order (preserving ELO from matchmaking)
+                let seats = players.iter()
+                    .map(|p| GamePlayer::new(p.chain_id, p.owner, p.name.clone(), p.elo))
+                    .collect();
+                match self.new_game(game_id, rules, seats) {
+                    Ok(game) => self.launch_game(game).await,
+                    Err(error) => log::error!("Cannot start match {}: {}", game_id, error),
                 }
+                self.prune_rematch_offers().await;
             }
 
-            LiarsDiceMessage::UpdateLeaderboard {
-                winner,
-                winner_name,
-                winner_new_elo,
-                loser,
-                loser_name,
-                loser_new_elo,
-            } => {
-                self.assert_master_chain(chain_type);
-                log::info!("Updating leaderboard - Winner: {} (ELO: {}), Loser: {} (ELO: {})",
-                    winner_name, winner_new_elo, loser_name, loser_new_elo);
-
-                // ✅ FIX: Load existing entry and update cumulatively
-                let mut winner_entry = self.state.leaderboard.get(&winner).await
-                    .expect("Failed to load winner leaderboard entry")
-                    .unwrap_or_else(|| SimpleLeaderboardEntry {
-                        player_id: Some(winner),
-                        player_name: winner_name.clone(),
-                        rank: 0,
-                        elo: STARTING_ELO,
-                        games_won: 0,
-                        games_played: 0,
-                        win_rate: 0,
-                    });
-
-                // Update cumulative stats for winner
-                winner_entry.games_won += 1;
-                winner_entry.games_played += 1;
-                winner_entry.elo = winner_new_elo;
-                // Calculate win rate in basis points (10000 = 100%)
-                winner_entry.win_rate = if winner_entry.games_played > 0 {
-                    (winner_entry.games_won * 10000) / winner_entry.games_played
-                } else {
-                    0
+            LiarsDiceMessage::CommitDice {
+                game_id,
+                player_chain,
+                commitment,
+            } => {
+                self.assert_game_chain(chain_type);
+                if !self.authorize_player(origin, player_chain, game_id, "CommitDice") {
+                    return;
+                }
+                log::info!("Received commitment from {:?} for game {}", player_chain, game_id);
+
+                let mut game = match self.load_game(game_id).await {
+                    Ok(game) => game,
+                    Err(error) => {
+                        self.reject_action(game_id, player_chain, "CommitDice", error);
+                        return;
+                    }
+                };
+                let timestamp = self.runtime.system_time();
+
+                let result = game.commit_dice(&player_chain, commitment).and_then(|()| {
+                    // Once all committed, no seed or share can change - collect the shares
+                    if !game.all_committed() {
+                        return Ok(false);
+                    }
+                    game.start_sharing(timestamp)?;
+                    log::info!("All players committed, collecting entropy shares");
+                    Ok(true)
+                });
+
+                match result {
+                    Ok(all_committed) => {
+                        self.save_game(&game).await;
+                        if all_committed {
+                            self.request_entropy(game).await;
+                        }
+                    }
+                    Err(error) => self.reject_action(game_id, player_chain, "CommitDice", error),
+                }
+            }
+
+            LiarsDiceMessage::ShareEntropy { game_id, player_chain, entropy } => {
+                self.assert_game_chain(chain_type);
+                if !self.authorize_player(origin, player_chain, game_id, "ShareEntropy") {
+                    return;
+                }
+                let mut game = match self.load_game(game_id).await {
+                    Ok(game) => game,
+                    Err(error) => {
+                        self.reject_action(game_id, player_chain, "ShareEntropy", error);
+                        return;
+                    }
+                };
+                let house_seed = self.state.house_seeds.get(&game_id).await.expect("Failed to load house seed");
+                let timestamp = self.runtime.system_time();
+
+                // A bad share disqualifies the player, which can end the game or complete the shares
+                if let Err(error) = game.share_entropy(&player_chain, entropy) {
+                    let disqualified = error == GameError::EntropyMismatch;
+                    self.reject_action(game_id, player_chain, "ShareEntropy", error);
+                    if !disqualified {
+                        return;
+                    }
+                }
+                if game.phase == GamePhase::GameOver {
+                    game.ended_at = Some(timestamp);
+                    self.finish_game(game).await;
+                    return;
+                }
+                if !game.all_shared() {
+                    self.save_game(&game).await;
+                    return;
+                }
+                let started = house_seed
+                    .ok_or(GameError::HouseSeedMismatch)
+                    .and_then(|seed| game.reveal_house_seed(seed))
+                    .and_then(|()| game.start_bidding(timestamp));
+                self.save_game(&game).await;
+                match started {
+                    Ok(()) => {
+                        log::info!("All entropy shared, round seed fixed, starting bidding phase");
+                        self.announce_bidding(game).await;
+                    }
+                    Err(error) => log::error!("Cannot start bidding in game {}: {}", game_id, error),
+                }
+            }
+
+            LiarsDiceMessage::MakeBid { game_id, player_chain, bid } => {
+                self.assert_game_chain(chain_type);
+                if !self.authorize_player(origin, player_chain, game_id, "MakeBid") {
+                    return;
+                }
+                log::info!("Bid from {:?}: {} x {}", player_chain, bid.quantity, bid.face.value());
+
+                // The bid is placed in the (authenticated) sender's name, at game chain time
+                // (the next player's deadline runs from it)
+                let bid = Bid {
+                    bidder: Some(player_chain),
+                    timestamp: Some(self.runtime.system_time()),
+                    ..bid
+                };
+
+                let result = match self.load_game(game_id).await {
+                    Ok(mut game) => game.make_bid(bid.clone()).map(|()| game),
+                    Err(error) => Err(error),
+                };
+                let game_state = match result {
+                    Ok(game_state) => game_state,
+                    Err(error) => {
+                        self.reject_action(game_id, player_chain, "MakeBid", error);
+                        return;
+                    }
+                };
+                self.save_game(&game_state).await;
+
+                let player_chains: Vec<ChainId> = game_state.players
+                    .iter()
+                    .filter_map(|p| p.chain_id)
+                    .collect();
+                for chain_id in player_chains {
+                    self.message_manager(
+                        chain_id,
+                        LiarsDiceMessage::BidMade {
+                            game: game_state.clone(),
+                            bidder: player_chain,
+                            bid: bid.clone(),
+                        },
+                    );
+                }
+
+                self.publish_game_event(&game_state, LiarsDiceEvent::BidUpdate { game_id, bid }).await;
+            }
+
+            LiarsDiceMessage::CallLiar { game_id, player_chain } => {
+                self.assert_game_chain(chain_type);
+                if !self.authorize_player(origin, player_chain, game_id, "CallLiar") {
+                    return;
+                }
+                log::info!("Liar called by {:?}", player_chain);
+                self.challenge_bid(game_id, player_chain, ChallengeKind::Liar).await;
+            }
+
+            LiarsDiceMessage::CallExact { game_id, player_chain } => {
+                self.assert_game_chain(chain_type);
+                if !self.authorize_player(origin, player_chain, game_id, "CallExact") {
+                    return;
+                }
+                log::info!("Calza called by {:?}", player_chain);
+                self.challenge_bid(game_id, player_chain, ChallengeKind::Exact).await;
+            }
+
+            LiarsDiceMessage::RevealDice {
+                game_id,
+                player_chain,
+                reveal,
+            } => {
+                self.assert_game_chain(chain_type);
+                if !self.authorize_player(origin, player_chain, game_id, "RevealDice") {
+                    return;
+                }
+                log::info!("Reveal from {:?}", player_chain);
+
+                let mut game = match self.load_game(game_id).await {
+                    Ok(game) => game,
+                    Err(error) => {
+                        self.reject_action(game_id, player_chain, "RevealDice", error);
+                        return;
+                    }
                 };
-                winner_entry.player_name = winner_name; // Update name in case it changed
-
-                // Load existing entry for loser
-                let mut loser_entry = self.state.leaderboard.get(&loser).await
-                    .expect("Failed to load loser leaderboard entry")
-                    .unwrap_or_else(|| SimpleLeaderboardEntry {
-                        player_id: Some(loser),
-                        player_name: loser_name.clone(),
-                        rank: 0,
-                        elo: STARTING_ELO,
-                        games_won: 0,
-                        games_played: 0,
-                        win_rate: 0,
-                    });
-
-                // Update stats for loser (games_won stays same, ELO decreases)
-                loser_entry.games_played += 1;
-                loser_entry.elo = loser_new_elo;
-                // Recalculate win rate in basis points (10000 = 100%)
-                loser_entry.win_rate = if loser_entry.games_played > 0 {
-                    (loser_entry.games_won * 10000) / loser_entry.games_played
+
+                // Verify the revealed seed against the commitment; the dice
+                // themselves are checked against the combined seeds at resolution
+                let result = game.record_reveal(&player_chain, reveal.clone());
+                // A disqualified cheater still counts as revealed
+                let should_resolve = game.phase == GamePhase::Revealing && game.all_revealed();
+
+                match result {
+                    Ok(()) => {
+                        log::info!("Valid reveal from {:?}", player_chain);
+
+                        self.publish_game_event(
+                            &game,
+                            LiarsDiceEvent::DiceRevealed {
+                                game_id,
+                                player: player_chain,
+                                dice: reveal.dice,
+                            },
+                        ).await;
+                    }
+                    Err(GameError::CommitmentMismatch) => {
+                        log::error!("CHEATER DETECTED: {:?} - invalid reveal!", player_chain);
+                        self.reject_action(game_id, player_chain, "RevealDice", GameError::CommitmentMismatch);
+                    }
+                    Err(error) => self.reject_action(game_id, player_chain, "RevealDice", error),
+                }
+
+                // Check if all revealed (a disqualification is kept either way)
+                if should_resolve {
+                    self.resolve_round(game).await;
                 } else {
-                    0
+                    self.save_game(&game).await;
+                }
+            }
+
+            LiarsDiceMessage::RequestRematch { game_id, player_chain } => {
+                self.assert_game_chain(chain_type);
+                if !self.authorize_player(origin, player_chain, game_id, "RequestRematch") {
+                    return;
+                }
+                self.prune_rematch_offers().await;
+
+                let offer = self.state.rematch_offers.get(&game_id).await.expect("Failed to load rematch offer");
+                let Some(mut offer) = offer else {
+                    self.reject_action(game_id, player_chain, "RequestRematch", GameError::NoRematchOffer);
+                    return;
+                };
+                match offer.accept(player_chain, self.runtime.system_time()) {
+                    Ok(true) => {
+                        self.state.rematch_offers.remove(&game_id).expect("Failed to remove rematch offer");
+                        match self.check_rematch(&offer).await {
+                            Ok(()) => self.start_rematch(offer).await,
+                            Err(error) => {
+                                log::info!("Rematch of game {} refused: {}", game_id, error);
+                                for player in offer.players.iter().filter_map(|p| p.chain_id) {
+                                    self.reject_action(game_id, player, "RequestRematch", error.clone());
+                                }
+                            }
+                        }
+                    }
+                    Ok(false) => {
+                        log::info!("Player {:?} wants a rematch of game {}", player_chain, game_id);
+                        for player in offer.players.iter().filter_map(|p| p.chain_id) {
+                            self.message_manager(player, LiarsDiceMessage::RematchOffered { offer: offer.clone() });
+                        }
+                        self.state.rematch_offers.insert(&game_id, offer).expect("Failed to store rematch offer");
+                    }
+                    Err(error) => self.reject_action(game_id, player_chain, "RequestRematch", error),
+                }
+            }
+
+            LiarsDiceMessage::PoolCapacity { capacity } => {
+                self.assert_game_chain(chain_type);
+                if !self.authorize_lobby(origin, None, "PoolCapacity") {
+                    return;
+                }
+                log::info!("Pool capacity set to {}", capacity);
+                self.state.pool_capacity.set(Some(capacity));
+            }
+
+            LiarsDiceMessage::PlayerBanned { player_chain, banned_until } => {
+                self.assert_game_chain(chain_type);
+                if !self.authorize_lobby(origin, None, "PlayerBanned") {
+                    return;
+                }
+                log::info!("Player {:?} banned until {:?}", player_chain, banned_until);
+                self.state.banned_players.insert(&player_chain, banned_until).expect("Failed to store ban");
+            }
+
+            LiarsDiceMessage::StakeLocked {
+                game_id,
+                player_chain,
+                amount,
+            } => {
+                self.assert_game_chain(chain_type);
+                if !self.authorize_player(origin, player_chain, game_id, "StakeLocked") {
+                    return;
+                }
+                // Stakes are only taken from seated players while the game is on
+                let result = match self.load_game(game_id).await {
+                    Ok(game) if game.get_player_by_chain(&player_chain).is_none() => Err(GameError::UnknownPlayer),
+                    Ok(_) => {
+                        let now = self.runtime.system_time();
+                        match self.state.stake_escrows.get(&game_id).await.expect("Failed to load stake escrow") {
+                            Some(mut escrow) => escrow.deposit(player_chain, amount, now).map(|()| escrow),
+                            // Not a staked game
+                            None => Err(GameError::StakeMismatch),
+                        }
+                    }
+                    Err(error) => Err(error),
+                };
+                match result {
+                    Ok(escrow) => {
+                        log::info!("Stake of {} from {:?} in escrow for game {}", amount, player_chain, game_id);
+                        self.state.stake_escrows.insert(&game_id, escrow).expect("Failed to store stake escrow");
+                    }
+                    Err(error) => {
+                        log::error!(
+                            "Returning stake of {} to {:?} for game {}: {}",
+                            amount, player_chain, game_id, error
+                        );
+                        self.pay_out(player_chain, amount);
+                        self.message_manager(player_chain, LiarsDiceMessage::StakeReturned { game_id, amount });
+                    }
+                }
+            }
+
+            LiarsDiceMessage::SpectatorJoined => {
+                self.assert_game_chain(chain_type);
+                let now = self.runtime.system_time();
+                self.state.spectators.insert(&origin, now).expect("Failed to add spectator");
+            }
+
+            LiarsDiceMessage::SpectatorLeft => {
+                self.assert_game_chain(chain_type);
+                self.state.spectators.remove(&origin).expect("Failed to remove spectator");
+            }
+
+            LiarsDiceMessage::PoolPing => {
+                self.assert_game_chain(chain_type);
+                if !self.authorize_lobby(origin, None, "PoolPing") {
+                    return;
+                }
+                self.message_manager(origin, LiarsDiceMessage::PoolHeartbeat);
+            }
+
+            LiarsDiceMessage::ClaimTimeout { game_id, player_chain } => {
+                self.assert_game_chain(chain_type);
+                if !self.authorize_player(origin, player_chain, game_id, "ClaimTimeout") {
+                    return;
+                }
+                log::info!("Timeout claimed by {:?} in game {}", player_chain, game_id);
+
+                // Only a seated player may claim; the deadline itself is checked by the engine
+                let seated = match self.load_game(game_id).await {
+                    Ok(game) if game.get_player_by_chain(&player_chain).is_some() => Ok(()),
+                    Ok(_) => Err(GameError::UnknownPlayer),
+                    Err(error) => Err(error),
+                };
+                if let Err(error) = seated {
+                    self.reject_action(game_id, player_chain, "ClaimTimeout", error);
+                    return;
+                }
+                if let Err(error) = self.handle_timeout(game_id).await {
+                    self.reject_action(game_id, player_chain, "ClaimTimeout", error);
+                }
+            }
+
+            LiarsDiceMessage::SweepTimeout { game_id } => {
+                self.assert_game_chain(chain_type);
+                if !self.authorize_lobby(origin, Some(game_id), "SweepTimeout") {
+                    return;
+                }
+                // The lobby cleared its copy of the deadline - report the current one again afterwards
+                self.state.reported_deadlines.remove(&game_id).expect("Failed to reset reported deadline");
+                if let Err(error) = self.handle_timeout(game_id).await {
+                    log::info!("Lobby sweep found no timeout to handle in game {}: {}", game_id, error);
+                }
+            }
+
+            LiarsDiceMessage::PlayerForfeit { game_id, player_chain } => {
+                self.assert_game_chain(chain_type);
+                if !self.authorize_player(origin, player_chain, game_id, "PlayerForfeit") {
+                    return;
+                }
+                log::info!("Player {:?} forfeited game {}", player_chain, game_id);
+
+                // Eliminate the forfeiting player
+                let result = match self.load_game(game_id).await {
+                    Ok(mut game) => game.forfeit(&player_chain).map(|()| game),
+                    Err(error) => Err(error),
                 };
-                loser_entry.player_name = loser_name; // Update name in case it changed
 
-                // Store updated entries (not overwriting - updating cumulative stats)
-                self.state.leaderboard.insert(&winner, winner_entry.clone())
-                    .expect("Failed to update winner leaderboard entry");
-                self.state.leaderboard.insert(&loser, loser_entry.clone())
-                    .expect("Failed to update loser leaderboard entry");
+                match result {
+                    // Nobody else is left at the table - the game is over
+                    Ok(mut game) if game.phase == GamePhase::GameOver => {
+                        log::info!("Player {:?} eliminated due to forfeit", player_chain);
+                        game.ended_at = Some(self.runtime.system_time());
+                        self.finish_game(game).await;
+                    }
+                    Ok(game) => {
+                        log::info!("Player {:?} eliminated due to forfeit", player_chain);
+                        self.save_game(&game).await;
+                    }
+                    Err(error) => self.reject_action(game_id, player_chain, "PlayerForfeit", error),
+                }
+            }
+
+            // ============================================
+            // MASTER CHAIN MESSAGES
+            // ============================================
+            LiarsDiceMessage::RequestLobbyInfo { user_chain } => {
+                self.assert_master_chain(chain_type);
+                log::info!("Lobby info requested by {:?}", user_chain);
+
+                // Get first lobby chain and send to user
+                let lobby_keys = self.state.lobby_chains.indices().await.expect("Failed to get lobby chains");
+                if let Some(lobby_chain) = lobby_keys.into_iter().next() {
+                    self.message_manager(
+                        user_chain,
+                        LiarsDiceMessage::LobbyInfo { lobby_chain },
+                    );
+                }
+            }
+
+            LiarsDiceMessage::UpdateLeaderboard { standings } => {
+                self.assert_master_chain(chain_type);
+                log::info!("Updating leaderboard for {} players", standings.len());
+
+                let mut entries = Vec::with_capacity(standings.len());
+                for standing in standings {
+                    let Some(player_chain) = standing.chain_id else {
+                        continue;
+                    };
+                    let new_elo = standing.new_elo();
+
+                    // ✅ FIX: Load existing entry and update cumulatively
+                    let mut entry = self.state.leaderboard.get(&player_chain).await
+                        .expect("Failed to load leaderboard entry")
+                        .unwrap_or_else(|| SimpleLeaderboardEntry {
+                            player_id: Some(player_chain),
+                            player_name: standing.name.clone(),
+                            rank: 0,
+                            elo: STARTING_ELO,
+                            games_won: 0,
+                            games_played: 0,
+                            win_rate: 0,
+                            cheats: 0,
+                        });
+
+                    // Only the winner's games_won goes up; everyone's ELO moves by placement
+                    if standing.placement == 1 {
+                        entry.games_won += 1;
+                    }
+                    entry.games_played += 1;
+                    if standing.cheated {
+                        entry.cheats += 1;
+                    }
+                    entry.elo = new_elo;
+                    // Calculate win rate in basis points (10000 = 100%)
+                    entry.win_rate = if entry.games_played > 0 {
+                        (entry.games_won * 10000) / entry.games_played
+                    } else {
+                        0
+                    };
+                    entry.player_name = standing.name; // Update name in case it changed
+
+                    self.state.leaderboard.insert(&player_chain, entry.clone())
+                        .expect("Failed to update leaderboard entry");
+                    entries.push(entry);
+                }
 
                 // Emit leaderboard update event
                 self.runtime.emit(
                     LIARS_DICE_STREAM_NAME.into(),
-                    &LiarsDiceEvent::LeaderboardUpdate {
-                        entries: vec![winner_entry, loser_entry],
-                    },
+                    &LiarsDiceEvent::LeaderboardUpdate { entries },
                 );
             }
         }
@@ -1152,51 +1998,27 @@ impl Contract for LiarsDiceContract {
 
     async fn process_streams(&mut self, updates: Vec<linera_sdk::linera_base_types::StreamUpdate>) {
         for update in updates {
-            assert_eq!(
-                update.stream_id.stream_name,
-                LIARS_DICE_STREAM_NAME.into(),
+            let stream_name = update.stream_id.stream_name.clone();
+            let spectator_feed = stream_name == SPECTATOR_STREAM_NAME.into();
+            assert!(
+                spectator_feed || stream_name == LIARS_DICE_STREAM_NAME.into(),
                 "Unexpected stream name"
             );
 
             for index in update.new_indices() {
                 let event: LiarsDiceEvent = self
                     .runtime
-                    .read_event(update.chain_id, LIARS_DICE_STREAM_NAME.into(), index);
+                    .read_event(update.chain_id, stream_name.clone(), index);
 
                 log::debug!("Received event from chain {}: {:?}", update.chain_id, event);
 
-                match event {
-                    LiarsDiceEvent::GameState { game } => {
-                        // Update user's view of game state
-                        self.state.channel_game_state.set(Some(game.clone()));
-                        log::info!("Updated game state: game_id={}, phase={:?}", game.game_id, game.phase);
-                    }
-                    LiarsDiceEvent::QueueUpdate { players_in_queue } => {
-                        self.state.queue_count.set(players_in_queue);
-                        log::info!("Queue updated: {} players waiting", players_in_queue);
-                    }
-                    LiarsDiceEvent::ProfileUpdate { profile } => {
-                        log::info!("Profile update received: {}", profile.name);
-                    }
-                    LiarsDiceEvent::BidUpdate { game_id, bid } => {
-                        log::info!("Bid update in game {}: {:?}", game_id, bid);
-                    }
-                    LiarsDiceEvent::LiarCalledEvent { game_id, caller } => {
-                        log::info!("Liar called in game {} by {:?}", game_id, caller);
-                    }
-                    LiarsDiceEvent::DiceRevealed { game_id, player, dice } => {
-                        log::info!("Dice revealed in game {} by {:?}: {:?}", game_id, player, dice);
-                    }
-                    LiarsDiceEvent::RoundEnded { game_id, loser, round } => {
-                        log::info!("Round {} ended in game {}, loser: {:?}", round, game_id, loser);
-                    }
-                    LiarsDiceEvent::GameEnded { game_id, winner, loser } => {
-                        log::info!("Game {} ended: winner={:?}, loser={:?}", game_id, winner, loser);
-                    }
-                    LiarsDiceEvent::LeaderboardUpdate { entries } => {
-                        log::info!("Leaderboard updated with {} entries", entries.len());
-                    }
+                // The (possibly delayed) spectator feed only updates the spectator view
+                if spectator_feed {
+                    self.spectate_event(&event).await;
+                    continue;
                 }
+
+                self.apply_event(event);
             }
         }
     }
@@ -1243,359 +2065,1273 @@ impl LiarsDiceContract {
         );
     }
 
-    // ============================================
-    // BANKROLL INTEGRATION HELPERS
-    // ============================================
-
-    /// Get balance from bankroll application
-    fn bankroll_get_balance(&mut self) -> Amount {
-        let owner = self.runtime.application_id().into();
-        let bankroll_app_id = self.get_bankroll();
-        let response = self.runtime.call_application(true, bankroll_app_id, &BankrollOperation::Balance { owner });
-        match response {
-            BankrollResponse::Balance(balance) => balance,
-            response => {
-                log::error!("Unexpected response from Bankroll application: {:?}", response);
-                Amount::ZERO
+    /// Update the user chain from a lobby or master event, or a game event sent to this player
+    fn apply_event(&mut self, event: LiarsDiceEvent) {
+        match event {
+            LiarsDiceEvent::GameState { game } => {
+                // A game chain hosts many games - only follow our own
+                if !self.is_current_game(game.game_id, "GameState") {
+                    return;
+                }
+                // Update user's view of game state
+                log::info!("Updated game state: game_id={}, phase={:?}", game.game_id, game.phase);
+                self.state.channel_game_state.set(Some(*game));
+            }
+            LiarsDiceEvent::QueueUpdate { players_in_queue } => {
+                self.state.queue_count.set(players_in_queue);
+                log::info!("Queue updated: {} players waiting", players_in_queue);
+            }
+            LiarsDiceEvent::ProfileUpdate { profile } => {
+                log::info!("Profile update received: {}", profile.name);
+            }
+            LiarsDiceEvent::BidUpdate { game_id, bid } => {
+                log::info!("Bid update in game {}: {:?}", game_id, bid);
+            }
+            LiarsDiceEvent::LiarCalledEvent { game_id, caller } => {
+                log::info!("Liar called in game {} by {:?}", game_id, caller);
+            }
+            LiarsDiceEvent::ExactCalledEvent { game_id, caller } => {
+                log::info!("Calza called in game {} by {:?}", game_id, caller);
+            }
+            LiarsDiceEvent::DiceRevealed { game_id, player, dice } => {
+                log::info!("Dice revealed in game {} by {:?}: {:?}", game_id, player, dice);
+            }
+            LiarsDiceEvent::RoundEnded { game_id, loser, gainer, round } => {
+                log::info!("Round {} ended in game {}, loser: {:?}, gainer: {:?}", round, game_id, loser, gainer);
+            }
+            LiarsDiceEvent::GameEnded { game_id, winner, standings } => {
+                log::info!("Game {} ended: winner={:?}, {} players placed", game_id, winner, standings.len());
+            }
+            LiarsDiceEvent::LeaderboardUpdate { entries } => {
+                log::info!("Leaderboard updated with {} entries", entries.len());
             }
         }
     }
 
-    /// Update balance in bankroll application
-    fn bankroll_update_balance(&mut self, amount: Amount) {
-        let owner = self.runtime.application_id().into();
-        let bankroll_app_id = self.get_bankroll();
-        let _ = self.runtime.call_application(true, bankroll_app_id, &BankrollOperation::UpdateBalance { owner, amount });
+    /// Is a game-chain message about the game this user is playing? Stale messages from earlier games are dropped
+    fn is_current_game(&self, game_id: GameId, message: &str) -> bool {
+        if *self.state.user_game_id.get() == Some(game_id) {
+            return true;
+        }
+        log::info!("Ignoring {} for game {}, which is not the current game", message, game_id);
+        false
     }
 
-    /// Mint tokens via bankroll application (master chain only)
-    fn bankroll_mint_token(&mut self, chain_id: ChainId, amount: Amount) {
-        let bankroll_app_id = self.get_bankroll();
-        let _ = self.runtime.call_application(true, bankroll_app_id, &BankrollOperation::MintToken { chain_id, amount });
-        log::info!("Minted {} tokens for chain {:?}", amount, chain_id);
+    /// Is this user waiting for the game a game chain announced?
+    /// Either the lobby is matching us (queue, room or challenge) or we have an open rematch on that chain
+    async fn expects_game(&self, game_chain: ChainId, game_id: GameId) -> bool {
+        if let Some(offer) = self.state.user_rematch.get() {
+            return offer.game_chain == Some(game_chain) && offer.game_id.next_rematch() == game_id;
+        }
+        if game_id.lobby_chain.is_none() || game_id.lobby_chain != *self.state.lobby_chain.get() {
+            log::info!("Ignoring game {} from another lobby", game_id);
+            return false;
+        }
+        let finding_match = self
+            .state
+            .user_profile
+            .get()
+            .as_ref()
+            .is_some_and(|profile| profile.status == UserStatus::FindingMatch);
+        let challenges = self.state.user_challenges.count().await.expect("Failed to count challenges");
+        finding_match || self.state.user_room.get().is_some() || challenges > 0
     }
 
-    /// Try to match players in the queue
-    async fn try_match_players(&mut self) {
-        let queue_count = *self.state.queue_count.get();
-        if queue_count < 2 {
+    /// The game chain and game id the user is currently playing on
+    fn user_game(&self) -> Option<(ChainId, GameId)> {
+        (*self.state.user_game_chain.get()).zip(*self.state.user_game_id.get())
+    }
+
+    /// Lock a game's stake from the bankroll balance and move it into the game chain's escrow
+    /// Without enough tokens the player cannot play the staked game and forfeits it
+    fn lock_stake(&mut self, game_chain: ChainId, game_id: GameId, stake: Amount) {
+        let player_chain = self.runtime.chain_id();
+        let balance = self.bankroll_get_balance();
+        if balance < stake {
+            log::error!("Balance {} cannot cover the stake of {} for game {} - forfeiting", balance, stake, game_id);
+            self.message_manager(game_chain, LiarsDiceMessage::PlayerForfeit { game_id, player_chain });
             return;
         }
+        let remaining = balance.saturating_sub(stake);
+        self.bankroll_update_balance(remaining);
+        self.bankroll_transfer_token_pot(stake, game_chain);
+        self.state.user_balance.set(remaining);
+        self.state.user_stake.set(stake);
+        self.message_manager(game_chain, LiarsDiceMessage::StakeLocked { game_id, player_chain, amount: stake });
+        log::info!("Locked stake of {} for game {}", stake, game_id);
+    }
 
-        // Get two players from queue
-        let player1 = match self.state.matchmaking_queue.front().await {
-            Ok(Some(p)) => p,
-            _ => return,
-        };
-        self.state.matchmaking_queue.delete_front();
+    /// Pay tokens out of this game chain's bankroll pool to a player's balance
+    /// Payouts are only ever pushed by the game chain, so nobody can claim from its pool
+    fn pay_out(&mut self, player_chain: ChainId, amount: Amount) {
+        self.bankroll_pay_out(amount, player_chain);
+        log::info!("Paid {} out to {:?}", amount, player_chain);
+    }
 
-        let player2 = match self.state.matchmaking_queue.front().await {
-            Ok(Some(p)) => p,
-            _ => {
-                // Put player1 back
-                self.state.matchmaking_queue.push_back(player1);
-                return;
+    /// Add a game's net token result to the player's stats
+    fn record_stake_result(&mut self, stake: Amount, payout: Amount) {
+        if let Some(ref mut profile) = *self.state.user_profile.get_mut() {
+            if payout > stake {
+                profile.stats.record_win_loss(payout.saturating_sub(stake), true);
+            } else if stake > payout {
+                profile.stats.record_win_loss(stake.saturating_sub(payout), false);
             }
-        };
-        self.state.matchmaking_queue.delete_front();
-
-        // Update queue count
-        let count = self.state.queue_count.get_mut();
-        *count = count.saturating_sub(2);
+        }
+    }
 
-        // Get available game chain (DEMO: use current chain if none available)
-        let game_chain = match self.state.available_game_chains.front().await {
-            Ok(Some(gc)) => {
-                self.state.available_game_chains.delete_front();
-                gc
-            },
-            _ => {
-                // DEMO: Use current chain as game chain for single-chain deployment
-                log::info!("No registered game chains, using current chain for game");
-                self.runtime.chain_id()
-            }
+    /// Stop watching the spectated game chain, if any
+    fn stop_spectating(&mut self) {
+        let Some(game_chain) = self.state.spectating.get_mut().take() else {
+            return;
         };
+        self.state.spectated_games.clear();
+        let app_id = self.runtime.application_id().forget_abi();
+        self.runtime.unsubscribe_from_events(game_chain, app_id, SPECTATOR_STREAM_NAME.into());
+        self.message_manager(game_chain, LiarsDiceMessage::SpectatorLeft);
+        if let Some(ref mut profile) = *self.state.user_profile.get_mut() {
+            if matches!(profile.status, UserStatus::SpectatingGame { .. }) {
+                profile.set_status(UserStatus::Idle);
+            }
+        }
+        log::info!("Stopped spectating game chain {:?}", game_chain);
+    }
 
-        // Track active game chain
-        let game_chain_info = abi::management::GameChainInfo::new(game_chain, self.runtime.system_time());
-        self.state.active_game_chains.insert(&game_chain, game_chain_info).expect("Failed to insert game chain");
-
-        // Create game ID
-        let game_id = self.runtime.system_time().micros();
-
-        log::info!(
-            "Matched {} vs {} on game chain {:?}",
-            player1.name, player2.name, game_chain
-        );
+    /// Fold an event from the watched game chain into the spectator view of its game
+    async fn spectate_event(&mut self, event: &LiarsDiceEvent) {
+        let game_id = match event {
+            LiarsDiceEvent::GameState { game } => game.game_id,
+            LiarsDiceEvent::BidUpdate { game_id, .. }
+            | LiarsDiceEvent::DiceRevealed { game_id, .. }
+            | LiarsDiceEvent::RoundEnded { game_id, .. }
+            | LiarsDiceEvent::GameEnded { game_id, .. } => *game_id,
+            _ => return,
+        };
+        let mut view = self
+            .state
+            .spectated_games
+            .get(&game_id)
+            .await
+            .expect("Failed to load spectator view")
+            .unwrap_or_else(|| SpectatorView::new(game_id));
+        match event.clone() {
+            LiarsDiceEvent::GameState { game } => view.update_game(*game),
+            LiarsDiceEvent::BidUpdate { bid, .. } => view.record_bid(bid),
+            LiarsDiceEvent::DiceRevealed { player, dice, .. } => view.record_reveal(player, dice),
+            LiarsDiceEvent::RoundEnded { loser, .. } => view.round_ended(loser),
+            LiarsDiceEvent::GameEnded { winner, .. } => view.game_ended(winner),
+            _ => {}
+        }
+        self.state.spectated_games.insert(&game_id, view).expect("Failed to store spectator view");
+    }
 
-        // Notify players of match
-        self.message_manager(
-            player1.chain_id,
-            LiarsDiceMessage::MatchFound {
-                game_chain,
-                game_id,
-                opponent_name: player2.name.clone(),
-                opponent_elo: player2.elo,
-            },
+    /// The user as a lobby entry (for the queue or a room)
+    fn profile_player(&mut self, table_size: u8) -> QueuedPlayer {
+        let now = self.runtime.system_time();
+        let profile = self.state.user_profile.get()
+            .as_ref()
+            .expect("Profile not set");
+
+        QueuedPlayer::new(
+            profile.chain_id.expect("No chain ID"),
+            profile.owner.expect("No owner"),
+            profile.name.clone(),
+            profile.elo,
+            table_size,
+            now,
+        )
+    }
+
+    /// Refuse to play at a stake tier the bankroll balance cannot cover
+    fn assert_can_stake(&mut self, stake_tier: StakeTier) {
+        let stake = stake_tier.stake();
+        if stake == Amount::ZERO {
+            return;
+        }
+        let balance = self.bankroll_get_balance();
+        self.state.user_balance.set(balance);
+        assert!(balance >= stake, "Balance {} cannot cover the {:?} tier stake of {}", balance, stake_tier, stake);
+    }
+
+    /// The room the user is in; room operations fail outside one
+    fn user_room_id(&self, action: &str) -> RoomId {
+        match self.state.user_room.get() {
+            Some(room) => room.room_id,
+            None => panic!("{} requires the user to be in a room", action),
+        }
+    }
+
+    /// Draw a random salt for a new room's password hash
+    fn draw_room_salt(&mut self) -> String {
+        let chain_id = self.runtime.chain_id();
+        let timestamp = self.runtime.system_time();
+        let nonce = *self.state.rng_nonce.get();
+        let seed_str = format!("{:?}_{}_{}_room", chain_id, timestamp.micros(), nonce);
+        self.state.rng_nonce.set(nonce + 1);
+        let salt = generate_random_salt(seed_str, String::new()).expect("Failed to generate room salt");
+        hash_to_hex(&salt)
+    }
+
+    // ============================================
+    // MESSAGE AUTHORIZATION (GAME CHAIN)
+    // ============================================
+
+    /// Check that a game-chain message comes from the player it acts for
+    /// Rejected attempts are recorded in the audit log
+    fn authorize_player(&mut self, origin: ChainId, player_chain: ChainId, game_id: GameId, action: &str) -> bool {
+        if origin == player_chain {
+            return true;
+        }
+        log::error!(
+            "UNAUTHORIZED {}: origin {:?} claimed to be player {:?}",
+            action, origin, player_chain
         );
+        self.record_unauthorized_attempt(origin, Some(player_chain), Some(game_id), action);
+        false
+    }
+
+    /// Check that a game-chain message comes from the lobby chain
+    fn authorize_lobby(&mut self, origin: ChainId, game_id: Option<GameId>, action: &str) -> bool {
+        let lobby_chain = self.get_lobby_chain();
+        if origin == lobby_chain {
+            return true;
+        }
+        log::error!(
+            "UNAUTHORIZED {}: origin {:?} is not the lobby chain {:?}",
+            action, origin, lobby_chain
+        );
+        self.record_unauthorized_attempt(origin, None, game_id, action);
+        false
+    }
+
+    /// Tell a player why the game chain refused their action
+    fn reject_action(&mut self, game_id: GameId, player_chain: ChainId, action: &str, error: GameError) {
+        log::error!("Rejected {} in game {} from {:?}: {}", action, game_id, player_chain, error);
         self.message_manager(
-            player2.chain_id,
-            LiarsDiceMessage::MatchFound {
-                game_chain,
-                game_id,
-                opponent_name: player1.name.clone(),
-                opponent_elo: player1.elo,
+            player_chain,
+            LiarsDiceMessage::ActionRejected {
+                game_id: Some(game_id),
+                action: action.to_string(),
+                error,
             },
         );
+    }
+
+    /// Append a rejected message to the audit log
+    fn record_unauthorized_attempt(
+        &mut self,
+        origin: ChainId,
+        claimed_player: Option<ChainId>,
+        game_id: Option<GameId>,
+        action: &str,
+    ) {
+        let id = *self.state.unauthorized_attempt_count.get();
+        let attempt = UnauthorizedAttempt {
+            id,
+            origin: Some(origin),
+            claimed_player,
+            action: action.to_string(),
+            game_id,
+            attempted_at: Some(self.runtime.system_time()),
+        };
+        self.state
+            .unauthorized_attempts
+            .insert(&id, attempt)
+            .expect("Failed to record unauthorized attempt");
+        self.state.unauthorized_attempt_count.set(id + 1);
+    }
+
+    // ============================================
+    // BANKROLL INTEGRATION HELPERS
+    // ============================================
+
+    /// Get balance from bankroll application
+    fn bankroll_get_balance(&mut self) -> Amount {
+        let owner = self.runtime.application_id().into();
+        let bankroll_app_id = self.get_bankroll();
+        let response = self.runtime.call_application(true, bankroll_app_id, &BankrollOperation::Balance { owner });
+        match response {
+            BankrollResponse::Balance(balance) => balance,
+            response => {
+                log::error!("Unexpected response from Bankroll application: {:?}", response);
+                Amount::ZERO
+            }
+        }
+    }
+
+    /// Update balance in bankroll application
+    fn bankroll_update_balance(&mut self, amount: Amount) {
+        let owner = self.runtime.application_id().into();
+        let bankroll_app_id = self.get_bankroll();
+        let _ = self.runtime.call_application(true, bankroll_app_id, &BankrollOperation::UpdateBalance { owner, amount });
+    }
+
+    /// Send tokens to another chain's bankroll pool
+    fn bankroll_transfer_token_pot(&mut self, amount: Amount, target_chain: ChainId) {
+        let bankroll_app_id = self.get_bankroll();
+        let _ = self.runtime.call_application(
+            true,
+            bankroll_app_id,
+            &BankrollOperation::TransferTokenPot { amount, target_chain },
+        );
+    }
+
+    /// Pay tokens from this chain's bankroll pool to this application's balance on another chain
+    fn bankroll_pay_out(&mut self, amount: Amount, target_chain: ChainId) {
+        let owner = self.runtime.application_id().into();
+        let bankroll_app_id = self.get_bankroll();
+        let _ = self.runtime.call_application(
+            true,
+            bankroll_app_id,
+            &BankrollOperation::PayOut { owner, amount, target_chain },
+        );
+    }
+
+    /// Mint tokens via bankroll application (master chain only)
+    fn bankroll_mint_token(&mut self, chain_id: ChainId, amount: Amount) {
+        let bankroll_app_id = self.get_bankroll();
+        let _ = self.runtime.call_application(true, bankroll_app_id, &BankrollOperation::MintToken { chain_id, amount });
+        log::info!("Minted {} tokens for chain {:?}", amount, chain_id);
+    }
+
+    /// Seat tables from the queue while the pool has free game slots
+    /// Players stay queued while every game chain is full or out of service
+    async fn try_match_players(&mut self) {
+        while self.seat_next_table().await {}
+    }
+
+    /// Try to seat one table from the queue on the game chain with the most free slots
+    /// Players wait for a table of their preferred size and a close enough rating; see `next_table`
+    async fn seat_next_table(&mut self) -> bool {
+        let waiting = self.queued_players().await;
+        if waiting.len() < LiarsDiceGame::MIN_PLAYERS {
+            return false;
+        }
+        let game_chain = match self.free_game_chain().await {
+            Some(game_chain) => game_chain,
+            None => {
+                log::info!("No free game slot, {} players keep waiting", waiting.len());
+                return false;
+            }
+        };
+
+        let now = self.runtime.system_time();
+        let Some(seats) = next_table(&waiting, self.state.matchmaking_params.get(), now) else {
+            return false;
+        };
+        let mut players = Vec::with_capacity(seats.len());
+        for index in seats {
+            let player = &waiting[index];
+            self.state.match_wait_stats.get_mut().record(player.wait_micros(now));
+            if let Some(player) = self.dequeue(player.chain_id).await {
+                players.push(player);
+            }
+        }
+        self.announce_queue().await;
+
+        // Everyone at the table queued for the same tier, which sets the stake
+        let mut rules = self.state.game_rules.get().clone();
+        rules.stake = players.first().map_or(Amount::ZERO, |p| p.stake_tier.stake());
+        self.start_match(game_chain, players, rules, None).await;
+        true
+    }
+
+    /// Tell a pooled chain how many games it may host, so it checks rematches without asking the lobby
+    fn push_pool_capacity(&mut self, info: &GameChainInfo) {
+        let Some(chain_id) = info.chain_id else {
+            return;
+        };
+        let taking_games = !info.draining && info.chain_status == ActivityStatus::Active;
+        let capacity = if taking_games { info.capacity } else { 0 };
+        self.message_manager(chain_id, LiarsDiceMessage::PoolCapacity { capacity });
+    }
+
+    /// Push a new matchmaking ban to every pooled chain, ahead of any rematch the player agrees to there
+    async fn push_ban(&mut self, player_chain: ChainId, banned_until: Timestamp) {
+        let game_chains = self.state.game_chains.indices().await.expect("Failed to get game chains");
+        for game_chain in game_chains {
+            self.message_manager(game_chain, LiarsDiceMessage::PlayerBanned { player_chain, banned_until });
+        }
+    }
+
+    /// End of the player's matchmaking ban, if they are banned (the player is told)
+    async fn check_ban(&mut self, player_chain: ChainId) -> Option<Timestamp> {
+        let now = self.runtime.system_time();
+        let record = self.state.cheat_records.get(&player_chain).await.expect("Failed to load cheat record");
+        let banned_until = record.filter(|r| r.is_banned(now)).and_then(|r| r.banned_until)?;
+        log::info!("Player {:?} is banned from matchmaking until {:?}", player_chain, banned_until);
+        self.message_manager(player_chain, LiarsDiceMessage::MatchmakingBanned { banned_until });
+        Some(banned_until)
+    }
+
+    /// Start a game for `players` (seated in order) under `rules` on `game_chain` and tell everyone about it
+    async fn start_match(
+        &mut self,
+        game_chain: ChainId,
+        players: Vec<QueuedPlayer>,
+        rules: GameRules,
+        room_id: Option<RoomId>,
+    ) -> GameId {
+        // Game ids are unique across lobbies: this lobby's chain plus its running match count
+        let index = *self.state.games_matched.get();
+        self.state.games_matched.set(index + 1);
+        let game_id = GameId::new(self.runtime.chain_id(), index);
+
+        // Take a slot on the game chain and track the game for timeout sweeps
+        let now = self.runtime.system_time();
+        if let Ok(Some(mut info)) = self.state.game_chains.get(&game_chain).await {
+            info.start_game(now);
+            self.state.game_chains.insert(&game_chain, info).expect("Failed to update game chain");
+        }
+        let mut hosted = HostedGame::new(game_id, game_chain, players.iter().map(|p| p.chain_id).collect(), now);
+        hosted.room_id = room_id;
+        self.seat_players(&hosted);
+
+        let names: Vec<&str> = players.iter().map(|p| p.name.as_str()).collect();
+        log::info!("Matched {} on game chain {:?}", names.join(" vs "), game_chain);
+
+        // Notify every player of the match
+        for player in &players {
+            let opponents: Vec<QueuedPlayer> = players.iter()
+                .filter(|p| p.chain_id != player.chain_id)
+                .cloned()
+                .collect();
+            self.message_manager(
+                player.chain_id,
+                LiarsDiceMessage::MatchFound {
+                    game_chain,
+                    game_id,
+                    opponents,
+                },
+            );
+        }
 
         // Assign match to game chain
         self.message_manager(
             game_chain,
             LiarsDiceMessage::AssignMatch {
                 game_id,
-                player1,
-                player2,
+                rules,
+                players,
             },
         );
+        game_id
     }
 
-    /// Resolve the round after all reveals
-    async fn resolve_round(&mut self) {
-        let timestamp = self.runtime.system_time();
+    /// Track a game the lobby started and the players seated at it
+    fn seat_players(&mut self, hosted: &HostedGame) {
+        for player_chain in &hosted.players {
+            self.state.seated_players.insert(player_chain, hosted.game_id).expect("Failed to seat player");
+        }
+        self.state.hosted_games.insert(&hosted.game_id, hosted.clone()).expect("Failed to track hosted game");
+    }
 
-        // Phase 1: Compute everything and modify game state in one borrow scope
-        enum ResolveOutcome {
-            None,
-            RoundEnd {
-                game_state: LiarsDiceGame,
-                player_chains: Vec<ChainId>,
-                loser: ChainId,
-                actual_count: u8,
-                bid_was_valid: bool,
-                game_id: u64,
-                round: u32,
-            },
-            GameOver {
-                game_state: LiarsDiceGame,
-                player_chains: Vec<ChainId>,
-                loser: ChainId,
-                winner: ChainId,
-                actual_count: u8,
-                bid_was_valid: bool,
-                game_id: u64,
-                elo_change: i32,
+    /// Free the seats of a game that ended (players may already sit at a newer game)
+    async fn unseat_players(&mut self, hosted: &HostedGame) {
+        for player_chain in &hosted.players {
+            let seated = self.state.seated_players.get(player_chain).await.expect("Failed to load seated player");
+            if seated == Some(hosted.game_id) {
+                self.state.seated_players.remove(player_chain).expect("Failed to unseat player");
+            }
+        }
+    }
+
+    /// Is the player seated at a game or waiting in a room?
+    async fn is_busy(&self, player_chain: ChainId) -> bool {
+        self.state.seated_players.contains_key(&player_chain).await.expect("Failed to check seated players")
+            || self.state.player_rooms.contains_key(&player_chain).await.expect("Failed to check rooms")
+    }
+
+    /// Challenges the player has issued that are still waiting for an answer
+    async fn open_challenges(&self, challenger: ChainId) -> usize {
+        let mut open = 0;
+        self.state
+            .challenges
+            .for_each_index_value(|_, challenge| {
+                if challenge.challenger.chain_id == challenger {
+                    open += 1;
+                }
+                Ok(())
+            })
+            .await
+            .expect("Failed to read challenges");
+        open
+    }
+
+    /// Answer a challenge and tell both players
+    fn close_challenge(&mut self, mut challenge: Challenge, status: ChallengeStatus) {
+        self.state.challenges.remove(&challenge.challenge_id).expect("Failed to remove challenge");
+        challenge.status = status;
+        let challenger = challenge.challenger.chain_id;
+        self.message_manager(challenger, LiarsDiceMessage::ChallengeUpdate { challenge: challenge.clone() });
+        self.message_manager(challenge.target_chain, LiarsDiceMessage::ChallengeUpdate { challenge });
+    }
+
+    /// Expire the challenges nobody answered in time
+    async fn expire_challenges(&mut self) {
+        let now = self.runtime.system_time();
+        let mut expired = Vec::new();
+        self.state
+            .challenges
+            .for_each_index_value(|_, challenge| {
+                if challenge.is_expired(now) {
+                    expired.push(challenge.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .expect("Failed to read challenges");
+        for challenge in expired {
+            log::info!("Challenge {} expired", challenge.challenge_id);
+            self.close_challenge(challenge, ChallengeStatus::Expired);
+        }
+    }
+
+    /// Load a room, telling the sender if it does not exist
+    async fn load_room(&mut self, player_chain: ChainId, room_id: RoomId, action: &str) -> Option<RoomInfo> {
+        let room = self.state.rooms.get(&room_id).await.expect("Failed to load room");
+        if room.is_none() {
+            self.reject_room_action(player_chain, Some(room_id), action, RoomError::RoomNotFound);
+        }
+        room
+    }
+
+    /// Store a room and send it (without its password hash) to everyone in it
+    fn publish_room(&mut self, room: RoomInfo) {
+        let shown = room.redacted();
+        for player in &room.players {
+            self.message_manager(player.chain_id, LiarsDiceMessage::RoomUpdate { room: shown.clone() });
+        }
+        let room_id = room.room_id;
+        self.state.rooms.insert(&room_id, room).expect("Failed to store room");
+    }
+
+    /// Take a player out of a room; the room closes when its last player leaves
+    async fn remove_from_room(&mut self, mut room: RoomInfo, player_chain: ChainId) -> Result<(), RoomError> {
+        room.remove_player(player_chain, self.runtime.system_time())?;
+        self.state.player_rooms.remove(&player_chain).expect("Failed to untrack room player");
+        self.message_manager(player_chain, LiarsDiceMessage::RoomLeft { room_id: room.room_id });
+        log::info!("Player {:?} left room {}", player_chain, room.room_id);
+
+        if room.players.is_empty() {
+            log::info!("Room {} closed", room.room_id);
+            self.state.rooms.remove(&room.room_id).expect("Failed to remove room");
+        } else {
+            self.publish_room(room);
+        }
+        Ok(())
+    }
+
+    /// Tell a player the lobby refused their room action
+    fn reject_room_action(&mut self, player_chain: ChainId, room_id: Option<RoomId>, action: &str, error: RoomError) {
+        log::info!("{} from {:?} rejected: {}", action, player_chain, error);
+        self.message_manager(
+            player_chain,
+            LiarsDiceMessage::RoomActionRejected {
+                room_id,
+                action: action.to_string(),
+                error,
             },
+        );
+    }
+
+    /// Players waiting in the lobby's queue, longest waiting first
+    async fn queued_players(&self) -> Vec<QueuedPlayer> {
+        // Positions are only ever increasing, so sorting them restores arrival order
+        let mut positions = self.state.queue_order.indices().await.expect("Failed to get queue order");
+        positions.sort_unstable();
+
+        let mut players = Vec::with_capacity(positions.len());
+        for position in positions {
+            let Some(player_chain) = self.state.queue_order.get(&position).await.expect("Failed to read queue order")
+            else {
+                continue;
+            };
+            if let Some(player) = self.state.matchmaking_queue.get(&player_chain).await.expect("Failed to read queue") {
+                players.push(player);
+            }
         }
+        players
+    }
 
-        let outcome = {
-            let game = self.state.current_game.get_mut();
-            if game.is_none() {
-                ResolveOutcome::None
-            } else {
-                let game = game.as_mut().unwrap();
+    /// Take a player out of the queue (both the entry and its place in line)
+    async fn dequeue(&mut self, player_chain: ChainId) -> Option<QueuedPlayer> {
+        let player = self.state.matchmaking_queue.get(&player_chain).await.expect("Failed to read queue")?;
+        self.state.matchmaking_queue.remove(&player_chain).expect("Failed to dequeue player");
+        self.state.queue_order.remove(&player.queue_position).expect("Failed to update queue order");
+        Some(player)
+    }
 
-                let bid = match &game.current_bid {
-                    Some(b) => b.clone(),
-                    None => return,
-                };
+    /// Tell subscribers how many players are waiting
+    async fn announce_queue(&mut self) {
+        let players_in_queue = self.state.matchmaking_queue.count().await.expect("Failed to count queue") as u32;
+        self.runtime.emit(
+            LIARS_DICE_STREAM_NAME.into(),
+            &LiarsDiceEvent::QueueUpdate { players_in_queue },
+        );
+    }
 
-                let actual_count = game.count_total_dice(bid.face, true);
-                let bid_was_valid = actual_count >= bid.quantity;
+    /// Pick the available game chain with the most free slots (the one idle longest on a tie)
+    async fn free_game_chain(&self) -> Option<ChainId> {
+        let game_chains = self.state.game_chains.indices().await.expect("Failed to get game chains");
+        let mut free: Option<(ChainId, u32, Option<Timestamp>)> = None;
+        for game_chain in game_chains {
+            if let Ok(Some(info)) = self.state.game_chains.get(&game_chain).await {
+                let slots = info.free_slots();
+                let better = free.is_none_or(|(_, most, since)| {
+                    slots > most || (slots == most && info.last_update < since)
+                });
+                if info.is_available() && better {
+                    free = Some((game_chain, slots, info.last_update));
+                }
+            }
+        }
+        free.map(|(game_chain, _, _)| game_chain)
+    }
 
-                // Determine loser
-                let loser = if bid_was_valid {
-                    game.liar_caller.clone().expect("No liar caller")
-                } else {
-                    bid.bidder.clone().expect("No bidder")
+    /// Ask the hosting game chain of every game whose phase deadline has passed to enforce it
+    /// The deadline is cleared once triggered; the game chain reports the next one after moving on
+    async fn sweep_timeouts(&mut self) {
+        let now = self.runtime.system_time();
+        let game_ids = self.state.hosted_games.indices().await.expect("Failed to get hosted games");
+
+        for game_id in game_ids {
+            let mut hosted = match self.state.hosted_games.get(&game_id).await {
+                Ok(Some(hosted)) => hosted,
+                _ => continue,
+            };
+            let Some(game_chain) = hosted.game_chain else {
+                continue;
+            };
+            if !hosted.is_overdue(now) {
+                continue;
+            }
+            log::info!("Game {} on {:?} is past its deadline, triggering timeout", game_id, game_chain);
+            hosted.deadline = None;
+            self.state.hosted_games.insert(&game_id, hosted).expect("Failed to update hosted game");
+            self.message_manager(game_chain, LiarsDiceMessage::SweepTimeout { game_id });
+        }
+
+        // Unanswered challenges run on the same clock
+        self.expire_challenges().await;
+    }
+
+    /// Draw a private dice seed for this round and send its commitments (seed and entropy share) to the game chain
+    /// The dice themselves are rolled once every share is in and the round seed is fixed
+    fn commit_round_seed(&mut self, round: u32) {
+        let chain_id = self.runtime.chain_id();
+        let timestamp = self.runtime.system_time();
+        let nonce = *self.state.rng_nonce.get();
+        // Mix private nonce into seed so observers can't predict it
+        let seed_str = format!("{:?}_{}_{}_round_{}", chain_id, timestamp.micros(), nonce, round);
+        self.state.rng_nonce.set(nonce + 1);
+
+        // Seed doubles as the commitment salt - CRITICAL for security
+        let seed = generate_random_salt(seed_str, String::new())
+            .expect("Failed to generate dice seed. Cannot proceed without secure randomness.");
+
+        // Store seed privately (NEVER sent to other chains until reveal)
+        self.state.user_salt.set(Some(seed));
+        self.state.user_dice.set(None);
+
+        log::info!("Generated dice seed for user {:?}, sending commitment to game chain", chain_id);
+        self.send_commitment(&seed);
+    }
+
+    /// Send the commitments to a dice seed to the game chain (only hashes, not the seed!)
+    fn send_commitment(&mut self, seed: &[u8; 32]) {
+        let chain_id = self.runtime.chain_id();
+        // Commitment hash = SHA-256(seed)
+        let dice_commitment = DiceCommitment::new(commit_seed(seed), commit_seed(&entropy_share(seed)));
+
+        if let Some((game_chain, game_id)) = self.user_game() {
+            self.message_manager(
+                game_chain,
+                LiarsDiceMessage::CommitDice {
+                    game_id,
+                    player_chain: chain_id,
+                    commitment: dice_commitment,
+                },
+            );
+        }
+    }
+
+    /// Draw the game chain's house seed for a round and return its commitment
+    /// Anyone syncing the chain can read (or recompute) it, so it only adds to the round seed:
+    /// the players' committed entropy shares are what keep it unpredictable
+    fn draw_house_seed(&mut self, game_id: GameId, round: u32) -> [u8; 32] {
+        let chain_id = self.runtime.chain_id();
+        let timestamp = self.runtime.system_time();
+        let nonce = *self.state.house_rng_nonce.get();
+        let seed_str = format!("{:?}_{}_{}_game_{}_round_{}", chain_id, timestamp.micros(), nonce, game_id, round);
+        self.state.house_rng_nonce.set(nonce + 1);
+
+        let seed = generate_random_salt(seed_str, String::new())
+            .expect("Failed to generate house seed. Cannot proceed without secure randomness.");
+        self.state.house_seeds.insert(&game_id, seed).expect("Failed to store house seed");
+
+        commit_seed(&seed)
+    }
+
+    /// Load a game hosted on this chain
+    async fn load_game(&self, game_id: GameId) -> Result<LiarsDiceGame, GameError> {
+        self.state.games.get(&game_id).await.expect("Failed to load game").ok_or(GameError::NoActiveGame)
+    }
+
+    /// Store a hosted game, and tell the lobby when its phase deadline changed so it can sweep stalled games
+    async fn save_game(&mut self, game: &LiarsDiceGame) {
+        let game_id = game.game_id;
+        self.state.games.insert(&game_id, game.clone()).expect("Failed to store game");
+
+        let deadline = game.phase_deadline();
+        let reported = self.state.reported_deadlines.get(&game_id).await.expect("Failed to load reported deadline");
+        if deadline == reported {
+            return;
+        }
+        match deadline {
+            Some(deadline) => self.state.reported_deadlines.insert(&game_id, deadline),
+            None => self.state.reported_deadlines.remove(&game_id),
+        }
+        .expect("Failed to record reported deadline");
+        let lobby_chain = self.get_lobby_chain();
+        self.message_manager(lobby_chain, LiarsDiceMessage::GameDeadline { game_id, deadline });
+    }
+
+    /// Challenge the current bid ("Liar!" or calza) and ask every player to reveal
+    async fn challenge_bid(&mut self, game_id: GameId, player_chain: ChainId, kind: ChallengeKind) {
+        let timestamp = self.runtime.system_time();
+        let action = match kind {
+            ChallengeKind::Liar => "CallLiar",
+            ChallengeKind::Exact => "CallExact",
+        };
+
+        let result = match self.load_game(game_id).await {
+            Ok(mut game) => {
+                let called = match kind {
+                    ChallengeKind::Liar => game.call_liar(player_chain, timestamp),
+                    ChallengeKind::Exact => game.call_exact(player_chain, timestamp),
                 };
+                called.map(|()| game)
+            }
+            Err(error) => Err(error),
+        };
 
-                // Apply penalty to loser
-                if let Some(player) = game.get_player_mut_by_chain(&loser) {
-                    player.lose_die();
-                }
+        match result {
+            Ok(game_state) => {
+                self.save_game(&game_state).await;
+                self.announce_challenge(game_state, player_chain, kind).await;
+            }
+            Err(error) => self.reject_action(game_id, player_chain, action, error),
+        }
+    }
 
-                // Update total dice
-                game.total_dice = game.players.iter().map(|p| p.dice_count).sum();
+    /// Ask every player to reveal after `caller` challenged the bid
+    async fn announce_challenge(&mut self, game_state: LiarsDiceGame, caller: ChainId, kind: ChallengeKind) {
+        let game_id = game_state.game_id;
+        let player_chains: Vec<ChainId> = game_state.players
+            .iter()
+            .filter_map(|p| p.chain_id)
+            .collect();
+
+        for chain_id in player_chains {
+            let message = match kind {
+                ChallengeKind::Liar => LiarsDiceMessage::LiarCalled {
+                    game: game_state.clone(),
+                    caller,
+                },
+                ChallengeKind::Exact => LiarsDiceMessage::ExactCalled {
+                    game: game_state.clone(),
+                    caller,
+                },
+            };
+            self.message_manager(chain_id, message);
+        }
 
-                // Collect player chains
-                let player_chains: Vec<ChainId> = game.players
-                    .iter()
-                    .filter_map(|p| p.chain_id)
-                    .collect();
+        let event = match kind {
+            ChallengeKind::Liar => LiarsDiceEvent::LiarCalledEvent {
+                game_id,
+                caller,
+            },
+            ChallengeKind::Exact => LiarsDiceEvent::ExactCalledEvent {
+                game_id,
+                caller,
+            },
+        };
+        self.publish_game_event(&game_state, event).await;
+    }
 
-                // Check for game over
-                let active_count = game.players.iter().filter(|p| !p.eliminated).count();
+    /// Ask every player for their entropy share now that all have committed
+    async fn request_entropy(&mut self, game_state: LiarsDiceGame) {
+        let player_chains: Vec<ChainId> = game_state.players
+            .iter()
+            .filter(|p| !p.eliminated)
+            .filter_map(|p| p.chain_id)
+            .collect();
+        for chain_id in player_chains {
+            self.message_manager(
+                chain_id,
+                LiarsDiceMessage::EntropyRequested { game: game_state.clone() },
+            );
+        }
+        self.publish_game_event(&game_state, LiarsDiceEvent::GameState { game: Box::new(game_state.clone()) }).await;
+    }
 
-                if active_count == 1 {
-                    // Game over!
-                    let winner_player = game.players.iter()
-                        .find(|p| !p.eliminated)
-                        .expect("No winner player");
-                    let winner = winner_player.chain_id.expect("No winner chain ID");
-                    let winner_elo = winner_player.elo;
+    /// Tell every player the round seed is fixed and bidding is open
+    async fn announce_bidding(&mut self, game_state: LiarsDiceGame) {
+        let player_chains: Vec<ChainId> = game_state.players
+            .iter()
+            .filter_map(|p| p.chain_id)
+            .collect();
+        for chain_id in player_chains {
+            self.message_manager(
+                chain_id,
+                LiarsDiceMessage::RoundSeedRevealed { game: game_state.clone() },
+            );
+        }
 
-                    let loser_player = game.players.iter()
-                        .find(|p| p.chain_id.as_ref() == Some(&loser))
-                        .expect("No loser player");
-                    let loser_elo = loser_player.elo;
+        // Emit game state
+        self.publish_game_event(&game_state, LiarsDiceEvent::GameState { game: Box::new(game_state.clone()) }).await;
+    }
 
-                    game.winner = Some(winner);
-                    game.phase = GamePhase::GameOver;
-                    game.ended_at = Some(timestamp);
+    /// Deal the next round after a stalled bidder ended this one without a challenge
+    async fn deal_next_round(&mut self, mut game: LiarsDiceGame, stalled: ChainId) {
+        let timestamp = self.runtime.system_time();
+        let game_id = game.game_id;
+        let next_round = game.round + 1;
+        let next_house_commitment = self.draw_house_seed(game_id, next_round);
 
-                    // Calculate ELO change using ACTUAL player ELOs
-                    let elo_change = calculate_elo_change(winner_elo, loser_elo, true);
-
-                    ResolveOutcome::GameOver {
-                        game_state: game.clone(),
-                        player_chains,
-                        loser,
-                        winner,
-                        actual_count,
-                        bid_was_valid,
-                        game_id: game.game_id,
-                        elo_change,
-                    }
-                } else {
-                    // Start new round
-                    let game_id = game.game_id;
-                    game.new_round();
-                    let round = game.round - 1;
-
-                    ResolveOutcome::RoundEnd {
-                        game_state: game.clone(),
-                        player_chains,
-                        loser,
-                        actual_count,
-                        bid_was_valid,
-                        game_id,
-                        round,
-                    }
+        if let Err(error) = game.new_round(timestamp) {
+            log::error!("Cannot start round {} of game {}: {}", next_round, game_id, error);
+            self.save_game(&game).await;
+            return;
+        }
+        game.set_house_commitment(next_house_commitment);
+        self.save_game(&game).await;
+
+        // Nothing was revealed: the stalled player is the round's loser
+        let player_chains: Vec<ChainId> = game.players
+            .iter()
+            .filter_map(|p| p.chain_id)
+            .collect();
+        for chain_id in player_chains {
+            self.message_manager(
+                chain_id,
+                LiarsDiceMessage::RoundResult {
+                    game: game.clone(),
+                    loser: Some(stalled),
+                    gainer: None,
+                    actual_count: 0,
+                    bid_was_valid: false,
+                },
+            );
+        }
+
+        self.publish_game_event(
+            &game,
+            LiarsDiceEvent::RoundEnded {
+                game_id,
+                loser: Some(stalled),
+                gainer: None,
+                round: next_round - 1,
+            },
+        ).await;
+    }
+
+    /// Penalize whoever is stalling a game past its phase deadline and move the game on
+    /// Shared by `CheckTimeout`, a player's `ClaimTimeout` and the lobby's sweep
+    async fn handle_timeout(&mut self, game_id: GameId) -> Result<(), GameError> {
+        let now = self.runtime.system_time();
+        let mut game = self.load_game(game_id).await?;
+        let house_seed = self.state.house_seeds.get(&game_id).await.expect("Failed to load house seed");
+
+        let result = game.check_timeout(now).and_then(|outcome| {
+            // Forfeits can leave only committed players - collect their shares
+            if game.phase == GamePhase::Committing && game.all_committed() {
+                game.start_sharing(now)?;
+            }
+            // Disqualifying the players who held back their shares fixes the round seed from the rest
+            if game.phase == GamePhase::SharingEntropy && game.all_shared() {
+                game.reveal_house_seed(house_seed.ok_or(GameError::HouseSeedMismatch)?)?;
+                game.start_bidding(now)?;
+            }
+            if game.phase == GamePhase::GameOver {
+                game.ended_at = Some(now);
+            }
+            Ok(outcome)
+        });
+        let outcome = match result {
+            Ok(outcome) => outcome,
+            Err(error) => {
+                // Keep anything already settled, and re-report the deadline to a sweeping lobby
+                self.save_game(&game).await;
+                return Err(error);
+            }
+        };
+        log::info!("Timeout in round {} of game {}: {:?}", game.round, game_id, outcome);
+
+        match (outcome, game.phase.clone()) {
+            (_, GamePhase::GameOver) => self.finish_game(game).await,
+            // Non-revealers are out - count the hands that were shown
+            (TimeoutOutcome::Reveal { .. }, _) => self.resolve_round(game).await,
+            // The stalled bidder called "Liar!" automatically
+            (TimeoutOutcome::Bid { stalled, .. }, GamePhase::Revealing) => {
+                self.save_game(&game).await;
+                self.announce_challenge(game, stalled, ChallengeKind::Liar).await;
+            }
+            // The stalled bidder's round ended without a challenge
+            (TimeoutOutcome::Bid { stalled, .. }, GamePhase::RoundEnd) => self.deal_next_round(game, stalled).await,
+            (_, GamePhase::Bidding) => {
+                self.save_game(&game).await;
+                self.announce_bidding(game).await;
+            }
+            (_, GamePhase::SharingEntropy) => {
+                self.save_game(&game).await;
+                self.request_entropy(game).await;
+            }
+            // Commit window reopened - prompt the players still missing to commit again
+            _ => {
+                self.save_game(&game).await;
+                let missing: Vec<ChainId> = game.players
+                    .iter()
+                    .filter(|p| !p.eliminated && p.commitment.is_none())
+                    .filter_map(|p| p.chain_id)
+                    .collect();
+                let player_chains: Vec<ChainId> = game.players.iter().filter_map(|p| p.chain_id).collect();
+                for chain_id in player_chains {
+                    let message = if missing.contains(&chain_id) {
+                        LiarsDiceMessage::CommitRequested { game: game.clone() }
+                    } else {
+                        let event = LiarsDiceEvent::GameState { game: Box::new(game.clone()) };
+                        LiarsDiceMessage::GameEvent { event }
+                    };
+                    self.message_manager(chain_id, message);
                 }
+                self.publish_game_event(&game, LiarsDiceEvent::GameState { game: Box::new(game.clone()) }).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolve the round after all reveals
+    async fn resolve_round(&mut self, mut game: LiarsDiceGame) {
+        let timestamp = self.runtime.system_time();
+        let game_id = game.game_id;
+
+        // The engine re-derives every revealed hand from the combined seeds
+        // before counting, disqualifying anyone whose dice were not rolled from them
+        let RoundOutcome {
+            loser,
+            gainer,
+            actual_count,
+            bid_was_valid,
+            ..
+        } = match game.resolve_round() {
+            Ok(outcome) => outcome,
+            Err(error) => {
+                log::error!("Cannot resolve round {} of game {}: {}", game.round, game_id, error);
+                self.save_game(&game).await;
+                return;
             }
         };
+        let round = game.round;
 
-        // Phase 2: Send messages and emit events (no more mutable borrow)
-        match outcome {
-            ResolveOutcome::None => {}
-            ResolveOutcome::RoundEnd {
-                game_state,
-                player_chains,
-                loser,
-                actual_count,
-                bid_was_valid,
+        if game.phase == GamePhase::GameOver {
+            // Game over!
+            game.ended_at = Some(timestamp);
+        } else {
+            // Start new round
+            let next_house_commitment = self.draw_house_seed(game_id, round + 1);
+            if let Err(error) = game.new_round(timestamp) {
+                log::error!("Cannot start round {} of game {}: {}", round + 1, game_id, error);
+                self.save_game(&game).await;
+                return;
+            }
+            game.set_house_commitment(next_house_commitment);
+        }
+
+        let player_chains: Vec<ChainId> = game.players
+            .iter()
+            .filter_map(|p| p.chain_id)
+            .collect();
+        for chain_id in player_chains {
+            self.message_manager(
+                chain_id,
+                LiarsDiceMessage::RoundResult {
+                    game: game.clone(),
+                    loser,
+                    gainer,
+                    actual_count,
+                    bid_was_valid,
+                },
+            );
+        }
+
+        if game.phase == GamePhase::GameOver {
+            self.finish_game(game).await;
+            return;
+        }
+        self.save_game(&game).await;
+
+        self.publish_game_event(
+            &game,
+            LiarsDiceEvent::RoundEnded {
                 game_id,
+                loser,
+                gainer,
                 round,
-            } => {
-                for chain_id in player_chains {
-                    self.message_manager(
-                        chain_id,
-                        LiarsDiceMessage::RoundResult {
-                            game: game_state.clone(),
-                            loser,
-                            actual_count,
-                            bid_was_valid,
-                        },
-                    );
-                }
+            },
+        ).await;
+    }
 
-                self.runtime.emit(
-                    LIARS_DICE_STREAM_NAME.into(),
-                    &LiarsDiceEvent::RoundEnded {
-                        game_id,
-                        loser,
-                        round,
+    /// Wrap up a finished game: send every seat its placement and ELO change,
+    /// free its slot in the lobby's pool and report the standings to the master chain
+    async fn finish_game(&mut self, game: LiarsDiceGame) {
+        let game_id = game.game_id;
+        let standings = game.standings();
+
+        // Settle the stakes; the rake stays in this chain's bankroll pool
+        let escrow = self.state.stake_escrows.get(&game_id).await.expect("Failed to load stake escrow");
+        let cheaters: Vec<ChainId> = game
+            .players
+            .iter()
+            .filter(|p| p.result == GameResult::Cheater)
+            .filter_map(|p| p.chain_id)
+            .collect();
+        let payouts = escrow.as_ref().map(|escrow| escrow.payouts(game.winner, &cheaters)).unwrap_or_default();
+        if let Some(escrow) = escrow {
+            log::info!("Game {} pot of {} settled, rake {}", game_id, escrow.pot(), escrow.rake());
+            self.state.stake_escrows.remove(&game_id).expect("Failed to remove stake escrow");
+        }
+
+        for standing in &standings {
+            if let Some(chain_id) = standing.chain_id {
+                let payout = payouts
+                    .iter()
+                    .find(|(player, _)| *player == chain_id)
+                    .map_or(Amount::ZERO, |(_, amount)| *amount);
+                if payout > Amount::ZERO {
+                    self.pay_out(chain_id, payout);
+                }
+                self.message_manager(
+                    chain_id,
+                    LiarsDiceMessage::GameResult {
+                        game: game.clone(),
+                        winner: game.winner,
+                        placement: standing.placement,
+                        elo_change: standing.elo_change,
+                        payout,
                     },
                 );
             }
-            ResolveOutcome::GameOver {
-                game_state,
-                player_chains,
-                loser,
-                winner,
-                actual_count,
-                bid_was_valid,
+        }
+
+        self.publish_game_event(
+            &game,
+            LiarsDiceEvent::GameEnded {
                 game_id,
-                elo_change,
-            } => {
-                for chain_id in &player_chains {
-                    self.message_manager(
-                        *chain_id,
-                        LiarsDiceMessage::RoundResult {
-                            game: game_state.clone(),
-                            loser,
-                            actual_count,
-                            bid_was_valid,
-                        },
-                    );
-                }
+                winner: game.winner,
+                standings: standings.clone(),
+            },
+        ).await;
 
-                for chain_id in player_chains {
-                    self.message_manager(
-                        chain_id,
-                        LiarsDiceMessage::GameResult {
-                            game: game_state.clone(),
-                            winner,
-                            loser,
-                            elo_change,
-                        },
-                    );
-                }
+        // ✅ FIX BUG #25: Send GameEnded to lobby chain to free the game's slot in the pool
+        let lobby_chain = self.get_lobby_chain();
+        let game_chain = self.runtime.chain_id();
+        self.message_manager(
+            lobby_chain,
+            LiarsDiceMessage::GameEnded {
+                game_chain,
+                game_id,
+                winner: game.winner,
+                cheaters,
+            },
+        );
+        log::info!("Sent GameEnded for game {} to lobby chain {:?}", game_id, lobby_chain);
+
+        // ✅ FIX BUG #26: Send UpdateLeaderboard to master chain
+        let master_chain = self.get_master_chain();
+        self.message_manager(master_chain, LiarsDiceMessage::UpdateLeaderboard { standings });
+        log::info!("Sent UpdateLeaderboard to master chain {:?}", master_chain);
+
+        // Drop the finished game; the lobby drops its deadline along with GameEnded
+        let games_hosted = self.state.games_hosted.get_mut();
+        *games_hosted += 1;
+        self.state.games.remove(&game_id).expect("Failed to remove game");
+        self.state.house_seeds.remove(&game_id).expect("Failed to remove house seed");
+        self.state.reported_deadlines.remove(&game_id).expect("Failed to remove reported deadline");
+
+        // Offer the same table a rematch for a while
+        let offer = RematchOffer::new(&game, game_chain, self.runtime.system_time());
+        if offer.players.len() < LiarsDiceGame::MIN_PLAYERS {
+            return;
+        }
+        for player in offer.players.iter().filter_map(|p| p.chain_id) {
+            self.message_manager(player, LiarsDiceMessage::RematchOffered { offer: offer.clone() });
+        }
+        self.state.rematch_offers.insert(&game_id, offer).expect("Failed to store rematch offer");
+    }
 
-                self.runtime.emit(
-                    LIARS_DICE_STREAM_NAME.into(),
-                    &LiarsDiceEvent::GameEnded {
-                        game_id,
-                        winner,
-                        loser,
-                    },
-                );
+    /// Set up a game with its seats (in order) and start its first round
+    fn new_game(
+        &mut self,
+        game_id: GameId,
+        rules: GameRules,
+        players: Vec<GamePlayer>,
+    ) -> Result<LiarsDiceGame, GameError> {
+        let mut game = LiarsDiceGame::with_rules(game_id, rules);
+        for player in players {
+            game.add_player(player)?;
+        }
+        game.start_game(self.runtime.system_time())?;
+        Ok(game)
+    }
 
-                // ✅ FIX BUG #25: Send GameEnded to lobby chain to return game chain to pool
-                let lobby_chain = self.get_lobby_chain();
-                let game_chain = self.runtime.chain_id();
-                self.message_manager(
-                    lobby_chain,
-                    LiarsDiceMessage::GameEnded {
-                        game_chain,
-                        winner,
-                        loser,
-                    },
-                );
-                log::info!("Sent GameEnded to lobby chain {:?} to return game chain {:?}", lobby_chain, game_chain);
+    /// Store a new game and tell its players to commit
+    async fn launch_game(&mut self, mut game: LiarsDiceGame) {
+        // Commit to the house seed before any player commits to theirs
+        let house_commitment = self.draw_house_seed(game.game_id, game.round);
+        game.set_house_commitment(house_commitment);
+
+        // Store game state
+        self.save_game(&game).await;
+        if game.rules.stake > Amount::ZERO {
+            let escrow = StakeEscrow::new(game.game_id, &game.rules);
+            self.state.stake_escrows.insert(&game.game_id, escrow).expect("Failed to store stake escrow");
+        }
 
-                // ✅ FIX BUG #26: Send UpdateLeaderboard to master chain
-                let winner_player = game_state.players.iter()
-                    .find(|p| p.chain_id == Some(winner));
-                let loser_player = game_state.players.iter()
-                    .find(|p| p.chain_id == Some(loser));
+        // Notify every seat
+        for player in game.players.iter().filter_map(|p| p.chain_id) {
+            self.message_manager(player, LiarsDiceMessage::GameStarted { game: game.clone() });
+        }
 
-                if let (Some(wp), Some(lp)) = (winner_player, loser_player) {
-                    let winner_new_elo = (wp.elo as i32 + elo_change) as u32;
-                    let loser_new_elo = (lp.elo as i32 - elo_change.abs()).max(100) as u32;
+        // Emit game state event
+        self.publish_game_event(&game, LiarsDiceEvent::GameState { game: Box::new(game.clone()) }).await;
+    }
 
-                    let master_chain = self.get_master_chain();
-                    self.message_manager(
-                        master_chain,
-                        LiarsDiceMessage::UpdateLeaderboard {
-                            winner,
-                            winner_name: wp.name.clone(),
-                            winner_new_elo,
-                            loser,
-                            loser_name: lp.name.clone(),
-                            loser_new_elo,
-                        },
-                    );
-                    log::info!("Sent UpdateLeaderboard to master chain {:?}", master_chain);
+    /// Emit a game event on the spectator feed once the game's delay allows
+    /// Players are not sent it: each action already reaches them in its own message
+    async fn publish_game_event(&mut self, game: &LiarsDiceGame, event: LiarsDiceEvent) {
+        let delay = game.rules.spectator_delay;
+        if delay == SpectatorDelay::Live {
+            self.runtime.emit(SPECTATOR_STREAM_NAME.into(), &event);
+            return;
+        }
+        // A round's result belongs to the round it ends, not the one dealt after it
+        let round = match &event {
+            LiarsDiceEvent::RoundEnded { round, .. } => *round,
+            _ => game.round,
+        };
+        let delayed = DelayedSpectatorEvent {
+            game_id: game.game_id,
+            round,
+            emitted_at: self.runtime.system_time(),
+            delay,
+            event,
+        };
+        let mut backlog = self.state.spectator_backlogs.get(&game.game_id).await
+            .expect("Failed to load spectator backlog")
+            .unwrap_or_default();
+        let key = (game.game_id, backlog.next_index);
+        backlog.next_index += 1;
+        self.state.spectator_backlog.insert(&key, delayed).expect("Failed to hold spectator event");
+        self.state.spectator_backlogs.insert(&game.game_id, backlog).expect("Failed to store spectator backlog");
+    }
+
+    /// Release held-back spectator events whose delay has passed, in the order they happened
+    /// A game's events fall due in order, so each game's backlog is read from its oldest event to the first one
+    /// still held back
+    async fn release_spectator_events(&mut self) {
+        let now = self.runtime.system_time();
+        let game_ids = self.state.spectator_backlogs.indices().await.expect("Failed to get spectator backlogs");
+
+        for game_id in game_ids {
+            let Some(mut backlog) = self.state.spectator_backlogs.get(&game_id).await
+                .expect("Failed to load spectator backlog")
+            else {
+                continue;
+            };
+            // A finished game's events are all released
+            let game = self.state.games.get(&game_id).await.expect("Failed to load game");
+            let current_round = game.map(|game| game.round);
+            while backlog.next_due < backlog.next_index {
+                let key = (game_id, backlog.next_due);
+                let delayed = self.state.spectator_backlog.get(&key).await.expect("Failed to read backlog");
+                if let Some(delayed) = delayed {
+                    if !delayed.delay.is_due(delayed.round, delayed.emitted_at, current_round, now) {
+                        break;
+                    }
+                    self.runtime.emit(SPECTATOR_STREAM_NAME.into(), &delayed.event);
+                    self.state.spectator_backlog.remove(&key).expect("Failed to release spectator event");
                 }
+                backlog.next_due += 1;
+            }
+            if backlog.next_due == backlog.next_index {
+                self.state.spectator_backlogs.remove(&game_id).expect("Failed to remove spectator backlog");
+            } else {
+                self.state.spectator_backlogs.insert(&game_id, backlog).expect("Failed to store spectator backlog");
+            }
+        }
+    }
+
+    /// Everyone agreed: play the finished game again, bypassing the lobby's queue
+    async fn start_rematch(&mut self, offer: RematchOffer) {
+        let game_id = offer.game_id.next_rematch();
+        if self.state.games.contains_key(&game_id).await.expect("Failed to check games") {
+            log::error!("Rematch {} is already being played", game_id);
+            return;
+        }
+        let game = match self.new_game(game_id, offer.rules, offer.players) {
+            Ok(game) => game,
+            Err(error) => {
+                log::error!("Cannot start rematch {}: {}", game_id, error);
+                return;
+            }
+        };
+
+        log::info!("Starting rematch {}", game_id);
+        let players = game.players.iter().filter_map(|p| p.chain_id).collect();
+        self.launch_game(game).await;
 
-                // Mark game chain as available again
-                self.state.game_chain_available.set(true);
-                let games_hosted = self.state.games_hosted.get_mut();
-                *games_hosted += 1;
-                self.state.current_game.set(None);
+        // The lobby tracks the rematch like any game it matched (before its first deadline report)
+        let lobby_chain = self.get_lobby_chain();
+        self.message_manager(lobby_chain, LiarsDiceMessage::RematchStarted { game_id, players });
+    }
+
+    /// Can an agreed rematch be played here? Checked against the bans and capacity the lobby pushed ahead of time
+    async fn check_rematch(&mut self, offer: &RematchOffer) -> Result<(), GameError> {
+        let now = self.runtime.system_time();
+        for player_chain in offer.players.iter().filter_map(|p| p.chain_id) {
+            let banned_until = self.state.banned_players.get(&player_chain).await.expect("Failed to load ban");
+            match banned_until {
+                Some(until) if now < until => return Err(GameError::PlayerBanned),
+                Some(_) => self.state.banned_players.remove(&player_chain).expect("Failed to remove ban"),
+                None => {}
             }
         }
+        let capacity = self.state.pool_capacity.get().unwrap_or(GameChainInfo::DEFAULT_CAPACITY);
+        let games = self.state.games.count().await.expect("Failed to count games");
+        if games >= capacity as usize {
+            return Err(GameError::NoFreeGameChain);
+        }
+        Ok(())
+    }
+
+    /// Drop rematch offers nobody took up in time
+    async fn prune_rematch_offers(&mut self) {
+        let now = self.runtime.system_time();
+        let mut expired = Vec::new();
+        self.state
+            .rematch_offers
+            .for_each_index_value(|game_id, offer| {
+                if offer.is_expired(now) {
+                    expired.push(game_id);
+                }
+                Ok(())
+            })
+            .await
+            .expect("Failed to read rematch offers");
+        for game_id in expired {
+            self.state.rematch_offers.remove(&game_id).expect("Failed to remove rematch offer");
+        }
     }
 
     // ============================================
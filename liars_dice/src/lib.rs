@@ -2,10 +2,11 @@
 // 4-Chain Architecture: Master (0), Lobby (1), Game (2), User (3)
 
 use abi::dice::{DiceCommitment, DiceReveal, PlayerDice};
-use abi::game::{Bid, GameId, LiarsDiceGame};
+use abi::game::{Bid, GameError, GameId, GameRules, LiarsDiceGame, PlayerStanding, RematchOffer, SpectatorDelay};
 use abi::leaderboard::SimpleLeaderboardEntry;
+use abi::management::{ActivityStatus, RoomError, RoomId, RoomInfo};
 // Note: GameChainInfo, LobbyChainInfo used in state.rs
-use abi::player::{PlayerProfile, QueuedPlayer};
+use abi::player::{Challenge, MatchmakingParams, PlayerProfile, QueuedPlayer, StakeTier};
 use async_graphql::{Request, Response};
 use bankroll::BankrollAbi;
 use linera_sdk::linera_base_types::{Amount, ApplicationId, ChainId, Timestamp};
@@ -18,6 +19,9 @@ use serde::{Deserialize, Serialize};
 /// Stream name for game events
 pub const LIARS_DICE_STREAM_NAME: &[u8] = b"liars_dice";
 
+/// Stream name for the spectator feed: game events, released after the game's spectator delay
+pub const SPECTATOR_STREAM_NAME: &[u8] = b"liars_dice_spectator";
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct LiarsDiceAbi;
 
@@ -39,35 +43,103 @@ pub enum LiarsDiceOperation {
     // ============================================
     /// Set or update player profile
     SetProfile { name: String },
-    /// Find a match through the lobby
-    FindMatch {},
+    /// Find a match through the lobby at a table of `table_size` players (2-6, default 2)
+    /// for a `stake_tier` buy-in (default free)
+    FindMatch {
+        table_size: Option<u8>,
+        stake_tier: Option<StakeTier>,
+    },
     /// Cancel matchmaking
     CancelMatch {},
-    /// Commit dice for the current round (sends hash to game chain)
-    CommitDice { commitment: [u8; 32] },
-    /// Reveal dice after liar is called
+    /// Commit to this round's dice seed (sends SHA-256(seed) and SHA-256(entropy share) to game chain)
+    CommitDice {
+        commitment: [u8; 32],
+        entropy_hash: [u8; 32],
+    },
+    /// Share this round's entropy once every player has committed
+    ShareEntropy { entropy: [u8; 32] },
+    /// Reveal dice after liar is called (salt is the committed dice seed)
     RevealDice { dice: Vec<u8>, salt: [u8; 32] },
     /// Make a bid
     MakeBid { quantity: u8, face: u8 },
     /// Call "Liar!" on the previous bidder
     CallLiar {},
+    /// Call "spot on" (calza): the previous bid is exactly right
+    CallExact {},
+    /// Claim that the player on the clock let their deadline pass (checked by the game chain)
+    ClaimTimeout {},
     /// Exit current game
     ExitGame {},
+    /// Ask to play the game that just ended again, with the same players and rules
+    RequestRematch {},
+    /// Watch the games on a game chain (follows its event stream)
+    SpectateGame { game_chain: ChainId },
+    /// Stop watching
+    StopSpectating {},
     /// Get balance from bankroll
     GetBalance {},
     /// Initial setup - configure user chain and subscribe to lobby
     InitialSetup { lobby_chain: ChainId },
+    /// Open a room in the lobby and host it (a password makes it private, the stake tier defaults to Free)
+    CreateRoom {
+        name: String,
+        max_players: Option<u8>,
+        password: Option<String>,
+        stake_tier: Option<StakeTier>,
+    },
+    /// Join a room at the room's stake tier (private rooms need the password, and the salt listed with the room)
+    JoinRoom {
+        room_id: RoomId,
+        password: Option<String>,
+        password_salt: Option<String>,
+        stake_tier: Option<StakeTier>,
+    },
+    /// Leave the current room
+    LeaveRoom {},
+    /// Host only: remove a player from the current room
+    KickFromRoom { player_chain: ChainId },
+    /// Host only: set the number of seats in the current room
+    SetRoomSeats { max_players: u8 },
+    /// Host only: start a game with everyone in the current room
+    StartRoomGame {},
+    /// Host only: set how far the spectator feed of the room's games lags behind
+    SetRoomSpectatorDelay { delay: SpectatorDelay },
+    /// Invite a specific player to a heads-up game (expires if not answered; the stake tier defaults to Free)
+    ChallengePlayer {
+        target_chain: ChainId,
+        stake_tier: Option<StakeTier>,
+    },
+    /// Accept a challenge received from another player
+    AcceptChallenge { challenge_id: u64 },
+    /// Decline a challenge received from another player
+    DeclineChallenge { challenge_id: u64 },
 
     // ============================================
     // LOBBY CHAIN OPERATIONS (instantiate_value = 1)
     // ============================================
-    // (Lobby chain receives messages, not direct operations)
+    /// Set the rules for games this lobby matches from now on
+    SetGameRules { rules: GameRules },
+    /// Set how closely matched players' ratings must be, and how fast the window widens
+    SetMatchmakingParams { params: MatchmakingParams },
+    /// Trigger timeouts on hosted games past their phase deadline and retry matchmaking (lobby chain owner only;
+    /// players claim their own timeouts with `ClaimTimeout`)
+    SweepTimeouts {},
+    /// Ping every pooled game chain; chains that missed the previous ping are marked inactive
+    CheckPoolHealth {},
+    /// Put a pooled game chain in or out of service (`MaintenanceMode` quarantines it)
+    SetGameChainStatus { chain_id: ChainId, status: ActivityStatus },
+    /// Remove a game chain from the pool (after the games it is hosting)
+    RemoveGameChain { chain_id: ChainId },
+    /// Set how many games a pooled game chain hosts at once
+    SetGameChainCapacity { chain_id: ChainId, capacity: u32 },
 
     // ============================================
     // GAME CHAIN OPERATIONS (instantiate_value = 2)
     // ============================================
-    /// Check for reveal timeout and eliminate non-revealers (can be called by anyone)
-    CheckTimeout {},
+    /// Penalize whoever is stalling a game's current phase past its deadline (can be called by anyone)
+    CheckTimeout { game_id: GameId },
+    /// Release spectator feed events whose delay has passed (can be called by anyone)
+    ReleaseSpectatorFeed {},
 
     // ============================================
     // MASTER CHAIN OPERATIONS (instantiate_value = 0)
@@ -98,35 +170,71 @@ pub enum LiarsDiceMessage {
     MatchFound {
         game_chain: ChainId,
         game_id: GameId,
-        opponent_name: String,
-        opponent_elo: u32,
+        opponents: Vec<QueuedPlayer>,
     },
-    /// Game has started, commit your dice
+    /// Game has started, commit your dice seed
     GameStarted { game: LiarsDiceGame },
+    /// The commit window was reopened and your commitment never arrived - commit again
+    CommitRequested { game: LiarsDiceGame },
+    /// Every player has committed - share your entropy for the round seed
+    EntropyRequested { game: LiarsDiceGame },
+    /// Every share is in and the round seed is fixed - roll your dice
+    RoundSeedRevealed { game: LiarsDiceGame },
+    /// Game state for a player with no dedicated message for the update (e.g. a reopened commit window)
+    GameEvent { event: LiarsDiceEvent },
     /// A bid was made
     BidMade { game: LiarsDiceGame, bidder: ChainId, bid: Bid },
     /// Someone called liar - reveal your dice
     LiarCalled { game: LiarsDiceGame, caller: ChainId },
+    /// Someone called calza - reveal your dice
+    ExactCalled { game: LiarsDiceGame, caller: ChainId },
     /// Reveal required (timeout warning)
     RevealRequired { deadline: Timestamp },
     /// Round result
     RoundResult {
         game: LiarsDiceGame,
-        loser: ChainId,
+        loser: Option<ChainId>,
+        gainer: Option<ChainId>,
         actual_count: u8,
         bid_was_valid: bool,
     },
-    /// Game is over
+    /// Game is over - this player's placement and ELO change
     GameResult {
         game: LiarsDiceGame,
-        winner: ChainId,
-        loser: ChainId,
+        winner: Option<ChainId>,
+        placement: u8,
         elo_change: i32,
+        /// Tokens paid out of the stake escrow to this player (zero when they lost or nothing was staked)
+        payout: Amount,
+    },
+    /// The game chain could not take this player's stake into escrow - it has been paid back
+    StakeReturned { game_id: GameId, amount: Amount },
+    /// The finished game can be played again (sent when it ends, and whenever a player agrees)
+    RematchOffered { offer: RematchOffer },
+    /// The game chain refused an action sent by this player
+    ActionRejected {
+        game_id: Option<GameId>,
+        action: String,
+        error: GameError,
     },
     /// Profile update confirmation
     ProfileUpdated { profile: PlayerProfile },
     /// Lobby chain info for subscription
     LobbyInfo { lobby_chain: ChainId },
+    /// The player's room changed (sent to everyone in it)
+    RoomUpdate { room: RoomInfo },
+    /// The player left the room or was kicked
+    RoomLeft { room_id: RoomId },
+    /// A challenge this player sent or received was issued, answered or expired
+    ChallengeUpdate { challenge: Challenge },
+    /// The lobby refused to queue this player after repeated cheating
+    MatchmakingBanned { banned_until: Timestamp },
+    /// The lobby refused a room or challenge action sent by this player
+    RoomActionRejected {
+        room_id: Option<RoomId>,
+        action: String,
+        error: RoomError,
+    },
 
     // ============================================
     // TO LOBBY CHAIN
@@ -135,58 +243,125 @@ pub enum LiarsDiceMessage {
     FindMatch { player: QueuedPlayer },
     /// Player wants to cancel matchmaking
     CancelMatch { player_chain: ChainId },
-    /// Game has ended, return game chain to pool
+    /// Game has ended, free its slot on the game chain
     GameEnded {
         game_chain: ChainId,
-        winner: ChainId,
-        loser: ChainId,
+        game_id: GameId,
+        winner: Option<ChainId>,
+        /// Players disqualified for cheating (each gets a strike)
+        cheaters: Vec<ChainId>,
     },
     /// Register a new game chain
     RegisterGameChain { chain_id: ChainId },
+    /// Current phase deadline of a game on the sending game chain
+    GameDeadline {
+        game_id: GameId,
+        deadline: Option<Timestamp>,
+    },
+    /// Game chain answers a health check ping
+    PoolHeartbeat,
+    /// A game chain started a rematch (the lobby seats its players and counts it against the chain's slots)
+    RematchStarted { game_id: GameId, players: Vec<ChainId> },
+    /// Player opens a room and becomes its host
+    CreateRoom {
+        host: QueuedPlayer,
+        name: String,
+        max_players: u8,
+        /// Password hash and the salt it was computed with
+        password_hash: Option<(String, String)>,
+    },
+    /// Player joins a room (`password_hash` as computed by `hash_room_password` with the room's salt)
+    JoinRoom {
+        room_id: RoomId,
+        player: QueuedPlayer,
+        password_hash: Option<String>,
+    },
+    /// Player leaves a room
+    LeaveRoom { room_id: RoomId },
+    /// Host removes a player from a room
+    KickFromRoom { room_id: RoomId, player_chain: ChainId },
+    /// Host sets the number of seats in a room
+    SetRoomSeats { room_id: RoomId, max_players: u8 },
+    /// Host starts a game with everyone in the room
+    StartRoomGame { room_id: RoomId },
+    /// Host sets the spectator delay of the room's games
+    SetRoomSpectatorDelay { room_id: RoomId, delay: SpectatorDelay },
+    /// Player challenges another player to a heads-up game
+    ChallengePlayer { challenger: QueuedPlayer, target_chain: ChainId },
+    /// Challenged player accepts (with their own lobby entry)
+    AcceptChallenge { challenge_id: u64, player: QueuedPlayer },
+    /// Challenged player declines
+    DeclineChallenge { challenge_id: u64 },
 
     // ============================================
     // TO GAME CHAIN
     // ============================================
-    /// Assign players to this game chain
+    /// Assign players to this game chain (seated in the given order)
     AssignMatch {
         game_id: GameId,
-        player1: QueuedPlayer,
-        player2: QueuedPlayer,
+        rules: GameRules,
+        players: Vec<QueuedPlayer>,
     },
-    /// Player commits their dice (hash only)
+    /// Player commits their dice seed (hash only)
     CommitDice {
+        game_id: GameId,
         player_chain: ChainId,
         commitment: DiceCommitment,
     },
+    /// Player shares their entropy for the round seed
+    ShareEntropy {
+        game_id: GameId,
+        player_chain: ChainId,
+        entropy: [u8; 32],
+    },
     /// Player makes a bid
     MakeBid {
+        game_id: GameId,
         player_chain: ChainId,
         bid: Bid,
     },
     /// Player calls liar
-    CallLiar { player_chain: ChainId },
+    CallLiar { game_id: GameId, player_chain: ChainId },
+    /// Player calls calza
+    CallExact { game_id: GameId, player_chain: ChainId },
     /// Player reveals their dice
     RevealDice {
+        game_id: GameId,
         player_chain: ChainId,
         reveal: DiceReveal,
     },
+    /// Player claims the game's current phase deadline has passed
+    ClaimTimeout { game_id: GameId, player_chain: ChainId },
+    /// Lobby sweep: the game's current phase deadline has passed
+    SweepTimeout { game_id: GameId },
+    /// Lobby health check
+    PoolPing,
+    /// A user chain started watching this game chain
+    SpectatorJoined,
+    /// A user chain stopped watching this game chain
+    SpectatorLeft,
+    /// Player locked the game's stake from their bankroll balance (the tokens follow as a bankroll pot)
+    StakeLocked {
+        game_id: GameId,
+        player_chain: ChainId,
+        amount: Amount,
+    },
     /// Player exits/forfeits
-    PlayerForfeit { player_chain: ChainId },
+    PlayerForfeit { game_id: GameId, player_chain: ChainId },
+    /// Player agrees to a rematch of a finished game
+    RequestRematch { game_id: GameId, player_chain: ChainId },
+    /// Lobby: games this chain may host at once (0 while it is out of service or draining), checked by rematches
+    PoolCapacity { capacity: u32 },
+    /// Lobby: a player is banned from matchmaking, and so from rematches, until `banned_until`
+    PlayerBanned { player_chain: ChainId, banned_until: Timestamp },
 
     // ============================================
     // TO MASTER CHAIN
     // ============================================
     /// Request lobby chain info
     RequestLobbyInfo { user_chain: ChainId },
-    /// Update leaderboard with game result
-    UpdateLeaderboard {
-        winner: ChainId,
-        winner_name: String,
-        winner_new_elo: u32,
-        loser: ChainId,
-        loser_name: String,
-        loser_new_elo: u32,
-    },
+    /// Update leaderboard with every seat's final standing
+    UpdateLeaderboard { standings: Vec<PlayerStanding> },
 }
 
 /// Application parameters
@@ -200,11 +375,29 @@ pub struct LiarsDiceParameters {
     pub bankroll: ApplicationId<BankrollAbi>,
 }
 
+/// Held-back spectator events of one game: the oldest still held, and the sequence number of the next one
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SpectatorBacklog {
+    pub next_due: u64,
+    pub next_index: u64,
+}
+
+/// A game event held back from the spectator feed until its delay has passed
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct DelayedSpectatorEvent {
+    pub game_id: GameId,
+    /// Round the game was in when the event happened
+    pub round: u32,
+    pub emitted_at: Timestamp,
+    pub delay: SpectatorDelay,
+    pub event: LiarsDiceEvent,
+}
+
 /// Events emitted for real-time updates
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum LiarsDiceEvent {
-    /// Game state update (sent to subscribers)
-    GameState { game: LiarsDiceGame },
+    /// Game state update (sent to subscribers; boxed to keep the other events small)
+    GameState { game: Box<LiarsDiceGame> },
     /// Matchmaking queue update
     QueueUpdate { players_in_queue: u32 },
     /// Leaderboard update
@@ -215,6 +408,8 @@ pub enum LiarsDiceEvent {
     BidUpdate { game_id: GameId, bid: Bid },
     /// Liar called
     LiarCalledEvent { game_id: GameId, caller: ChainId },
+    /// Calza called
+    ExactCalledEvent { game_id: GameId, caller: ChainId },
     /// Dice revealed
     DiceRevealed {
         game_id: GameId,
@@ -224,13 +419,14 @@ pub enum LiarsDiceEvent {
     /// Round ended
     RoundEnded {
         game_id: GameId,
-        loser: ChainId,
+        loser: Option<ChainId>,
+        gainer: Option<ChainId>,
         round: u32,
     },
     /// Game ended
     GameEnded {
         game_id: GameId,
-        winner: ChainId,
-        loser: ChainId,
+        winner: Option<ChainId>,
+        standings: Vec<PlayerStanding>,
     },
 }
@@ -4,12 +4,17 @@ mod state;
 
 use std::sync::Arc;
 
-use abi::game::LiarsDiceGame;
+use abi::game::{
+    GameId, GameRules, LiarsDiceGame, LiveGame, RejectedAction, RematchOffer, SpectatorView, StakeEscrow,
+};
 use abi::leaderboard::SimpleLeaderboardEntry;
-use abi::player::PlayerProfile;
+use abi::management::{
+    GameChainInfo, GameChainPoolStatus, HostedGame, RejectedRoomAction, RoomId, RoomInfo, UnauthorizedAttempt,
+};
+use abi::player::{Challenge, CheatRecord, MatchmakingParams, PlayerProfile, QueueStats};
 use async_graphql::{EmptySubscription, Object, Schema};
 use liars_dice::LiarsDiceOperation;
-use linera_sdk::linera_base_types::ChainId;
+use linera_sdk::linera_base_types::{Amount, ChainId, Timestamp};
 use linera_sdk::{
     graphql::GraphQLMutationRoot, linera_base_types::WithServiceAbi, views::View, Service,
     ServiceRuntime,
@@ -62,6 +67,31 @@ struct QueryRoot {
     runtime: Arc<ServiceRuntime<LiarsDiceService>>,
 }
 
+impl QueryRoot {
+    async fn pooled_game_chains(&self) -> Vec<GameChainInfo> {
+        let keys = self
+            .state
+            .game_chains
+            .indices()
+            .await
+            .expect("Failed to get game chain keys");
+
+        let mut chains = Vec::new();
+        for key in keys {
+            if let Some(info) = self
+                .state
+                .game_chains
+                .get(&key)
+                .await
+                .expect("Failed to get game chain")
+            {
+                chains.push(info);
+            }
+        }
+        chains
+    }
+}
+
 #[Object]
 impl QueryRoot {
     /// Get the chain type (0=Master, 1=Lobby, 2=Game, 3=User)
@@ -87,6 +117,11 @@ impl QueryRoot {
         self.state.channel_game_state.get().clone()
     }
 
+    /// Get the last action the game chain refused, and why
+    async fn get_last_rejection(&self) -> Option<RejectedAction> {
+        self.state.last_rejection.get().clone()
+    }
+
     /// Get the lobby chain ID
     async fn get_lobby_chain(&self) -> Option<ChainId> {
         *self.state.lobby_chain.get()
@@ -97,27 +132,255 @@ impl QueryRoot {
         *self.state.user_game_chain.get()
     }
 
+    /// Get the user's current game id (on their game chain)
+    async fn get_user_game_id(&self) -> Option<GameId> {
+        *self.state.user_game_id.get()
+    }
+
+    /// Get the game chain the user is watching
+    async fn get_spectating(&self) -> Option<ChainId> {
+        *self.state.spectating.get()
+    }
+
+    /// Get what the user has seen of the games on the watched chain
+    async fn get_spectated_games(&self) -> Vec<SpectatorView> {
+        let mut views = Vec::new();
+        self.state
+            .spectated_games
+            .for_each_index_value(|_, view| {
+                views.push(view.into_owned());
+                Ok(())
+            })
+            .await
+            .expect("Failed to get spectator views");
+        views
+    }
+
+    /// Get the stake the user locked for the current game
+    async fn get_user_stake(&self) -> Amount {
+        *self.state.user_stake.get()
+    }
+
+    /// Get the open rematch offer for the user's last game
+    async fn get_user_rematch(&self) -> Option<RematchOffer> {
+        self.state.user_rematch.get().clone()
+    }
+
+    /// Get the room the user is in
+    async fn get_user_room(&self) -> Option<RoomInfo> {
+        self.state.user_room.get().clone()
+    }
+
+    /// Get the end of the user's matchmaking ban, if the lobby refused to queue them
+    async fn get_matchmaking_ban(&self) -> Option<Timestamp> {
+        *self.state.matchmaking_banned_until.get()
+    }
+
+    /// Get the pending challenges the user sent or received
+    async fn get_user_challenges(&self) -> Vec<Challenge> {
+        let mut challenges = Vec::new();
+        self.state
+            .user_challenges
+            .for_each_index_value(|_, challenge| {
+                challenges.push(challenge.into_owned());
+                Ok(())
+            })
+            .await
+            .expect("Failed to get challenges");
+        challenges
+    }
+
+    /// Get the last room action the lobby refused, and why
+    async fn get_last_room_rejection(&self) -> Option<RejectedRoomAction> {
+        self.state.last_room_rejection.get().clone()
+    }
+
     // ============================================
     // LOBBY CHAIN QUERIES
     // ============================================
 
-    /// Get the number of players in matchmaking queue
+    /// Get the number of players in matchmaking queue (as last announced by the lobby, on user chains)
     async fn get_queue_count(&self) -> u32 {
-        *self.state.queue_count.get()
+        if *self.state.chain_type.get() == 3 {
+            return *self.state.queue_count.get();
+        }
+        self.state
+            .matchmaking_queue
+            .count()
+            .await
+            .expect("Failed to count matchmaking queue") as u32
+    }
+
+    /// Get the rules new matches are played under
+    async fn get_game_rules(&self) -> GameRules {
+        self.state.game_rules.get().clone()
+    }
+
+    /// Get the rating window matchmaking uses and how fast it widens
+    async fn get_matchmaking_params(&self) -> MatchmakingParams {
+        self.state.matchmaking_params.get().clone()
+    }
+
+    /// Get how long queued players have been waiting, and how long seated players waited
+    async fn get_queue_stats(&self) -> QueueStats {
+        let mut queue = Vec::new();
+        self.state
+            .matchmaking_queue
+            .for_each_index_value(|_, player| {
+                queue.push(player.into_owned());
+                Ok(())
+            })
+            .await
+            .expect("Failed to read matchmaking queue");
+        QueueStats::new(&queue, self.state.match_wait_stats.get(), self.runtime.system_time())
+    }
+
+    /// Get every game chain in the lobby's pool
+    async fn get_game_chain_pool(&self) -> Vec<GameChainInfo> {
+        self.pooled_game_chains().await
+    }
+
+    /// Get a summary of the game chain pool (available, in use, draining, unhealthy)
+    async fn get_game_chain_pool_status(&self) -> GameChainPoolStatus {
+        GameChainPoolStatus::from_chains(&self.pooled_game_chains().await)
+    }
+
+    /// Get every game in progress on the pool's chains, with its phase deadline
+    async fn get_hosted_games(&self) -> Vec<HostedGame> {
+        let keys = self
+            .state
+            .hosted_games
+            .indices()
+            .await
+            .expect("Failed to get hosted game keys");
+
+        let mut games = Vec::new();
+        for key in keys {
+            if let Some(game) = self
+                .state
+                .hosted_games
+                .get(&key)
+                .await
+                .expect("Failed to get hosted game")
+            {
+                games.push(game);
+            }
+        }
+        games
+    }
+
+    /// List the lobby's rooms (private rooms are listed without their password hash)
+    async fn get_rooms(&self, joinable_only: Option<bool>) -> Vec<RoomInfo> {
+        let joinable_only = joinable_only.unwrap_or(false);
+        let mut rooms = Vec::new();
+        self.state
+            .rooms
+            .for_each_index_value(|_, room| {
+                if !joinable_only || room.is_joinable() {
+                    rooms.push(room.redacted());
+                }
+                Ok(())
+            })
+            .await
+            .expect("Failed to get rooms");
+        rooms
+    }
+
+    /// Get a room by id (without its password hash)
+    async fn get_room(&self, room_id: RoomId) -> Option<RoomInfo> {
+        let room = self.state.rooms.get(&room_id).await.expect("Failed to get room");
+        room.map(|room| room.redacted())
+    }
+
+    /// Get a player's cheat strikes and matchmaking ban
+    async fn get_cheat_record(&self, player_chain: ChainId) -> Option<CheatRecord> {
+        self.state.cheat_records.get(&player_chain).await.expect("Failed to get cheat record")
     }
 
     // ============================================
     // GAME CHAIN QUERIES
     // ============================================
 
-    /// Get the current game on this game chain
-    async fn get_current_game(&self) -> Option<LiarsDiceGame> {
-        self.state.current_game.get().clone()
+    /// Get a game in progress on this game chain
+    /// Served live: the spectator delay only holds back the spectator stream, not the chain's state
+    async fn get_game(&self, game_id: GameId) -> Option<LiarsDiceGame> {
+        self.state.games.get(&game_id).await.expect("Failed to get game")
+    }
+
+    /// Get every game in progress on this game chain (live, like `get_game`)
+    async fn get_games(&self) -> Vec<LiarsDiceGame> {
+        let keys = self
+            .state
+            .games
+            .indices()
+            .await
+            .expect("Failed to get game keys");
+
+        let mut games = Vec::new();
+        for key in keys {
+            if let Some(game) = self
+                .state
+                .games
+                .get(&key)
+                .await
+                .expect("Failed to get game")
+            {
+                games.push(game);
+            }
+        }
+        games
+    }
+
+    /// Get the number of user chains watching this game chain
+    async fn get_spectator_count(&self) -> u32 {
+        self.state.spectators.count().await.expect("Failed to count spectators") as u32
     }
 
-    /// Check if this game chain is available
-    async fn is_game_chain_available(&self) -> bool {
-        *self.state.game_chain_available.get()
+    /// List the games being played on this game chain, to pick one to watch
+    async fn get_live_games(&self) -> Vec<LiveGame> {
+        let game_chain = self.runtime.chain_id();
+        let chain_spectators = self.state.spectators.count().await.expect("Failed to count spectators") as u32;
+        let mut games = Vec::new();
+        self.state
+            .games
+            .for_each_index_value(|_, game| {
+                games.push(LiveGame::new(&game, game_chain, chain_spectators));
+                Ok(())
+            })
+            .await
+            .expect("Failed to get games");
+        games
+    }
+
+    /// Get the stakes held in escrow for the games on this chain
+    async fn get_stake_escrows(&self) -> Vec<StakeEscrow> {
+        let mut escrows = Vec::new();
+        self.state
+            .stake_escrows
+            .for_each_index_value(|_, escrow| {
+                escrows.push(escrow.into_owned());
+                Ok(())
+            })
+            .await
+            .expect("Failed to get stake escrows");
+        escrows
+    }
+
+    /// Get the rematch offers open on this game chain
+    async fn get_rematch_offers(&self) -> Vec<RematchOffer> {
+        let now = self.runtime.system_time();
+        let mut offers = Vec::new();
+        self.state
+            .rematch_offers
+            .for_each_index_value(|_, offer| {
+                if !offer.is_expired(now) {
+                    offers.push(offer.into_owned());
+                }
+                Ok(())
+            })
+            .await
+            .expect("Failed to get rematch offers");
+        offers
     }
 
     /// Get total games hosted on this chain
@@ -125,6 +388,32 @@ impl QueryRoot {
         *self.state.games_hosted.get()
     }
 
+    /// Get rejected messages whose origin did not match the claimed sender (oldest first)
+    async fn get_unauthorized_attempts(&self) -> Vec<UnauthorizedAttempt> {
+        let keys = self
+            .state
+            .unauthorized_attempts
+            .indices()
+            .await
+            .expect("Failed to get unauthorized attempt keys");
+
+        let mut attempts = Vec::new();
+        for key in keys {
+            if let Some(attempt) = self
+                .state
+                .unauthorized_attempts
+                .get(&key)
+                .await
+                .expect("Failed to get unauthorized attempt")
+            {
+                attempts.push(attempt);
+            }
+        }
+
+        attempts.sort_by_key(|a| a.id);
+        attempts
+    }
+
     // ============================================
     // MASTER CHAIN QUERIES
     // ============================================
@@ -2,13 +2,16 @@
 // Multi-chain state: Master (0), Lobby (1), Game (2), User (3)
 
 use abi::dice::PlayerDice;
-use abi::game::LiarsDiceGame;
+use abi::game::{GameId, GameRules, LiarsDiceGame, RejectedAction, RematchOffer, SpectatorView, StakeEscrow};
 use abi::leaderboard::SimpleLeaderboardEntry;
-use abi::management::{GameChainInfo, LobbyChainInfo};
-use abi::player::{PlayerProfile, QueuedPlayer};
+use abi::management::{
+    GameChainInfo, HostedGame, LobbyChainInfo, RejectedRoomAction, RoomId, RoomInfo, UnauthorizedAttempt,
+};
+use abi::player::{Challenge, CheatRecord, MatchWaitStats, MatchmakingParams, PlayerProfile, QueuedPlayer};
 use bankroll::BankrollAbi;
-use linera_sdk::linera_base_types::{Amount, ApplicationId, ChainId};
-use linera_sdk::views::{linera_views, MapView, QueueView, RegisterView, RootView, ViewStorageContext};
+use liars_dice::{DelayedSpectatorEvent, SpectatorBacklog};
+use linera_sdk::linera_base_types::{Amount, ApplicationId, ChainId, Timestamp};
+use linera_sdk::views::{linera_views, MapView, RegisterView, RootView, ViewStorageContext};
 
 #[derive(RootView, async_graphql::SimpleObject)]
 #[view(context = ViewStorageContext)]
@@ -32,26 +35,77 @@ pub struct LiarsDiceState {
     // ============================================
     // LOBBY CHAIN STATE (instantiate_value = 1)
     // ============================================
-    /// Matchmaking queue
-    pub matchmaking_queue: QueueView<QueuedPlayer>,
-    /// Available game chains pool
-    pub available_game_chains: QueueView<ChainId>,
-    /// Game chains currently in use
-    pub active_game_chains: MapView<ChainId, GameChainInfo>,
-    /// Queue count for quick access
-    pub queue_count: RegisterView<u32>,
+    /// Matchmaking queue: waiting players by chain (one entry per player)
+    pub matchmaking_queue: MapView<ChainId, QueuedPlayer>,
+    /// Order of the queue: queue position -> waiting player
+    pub queue_order: MapView<u64, ChainId>,
+    /// Position given to the next queued player
+    pub next_queue_position: RegisterView<u64>,
+    /// Game chain pool: every registered game chain, idle or hosting games
+    pub game_chains: MapView<ChainId, GameChainInfo>,
+    /// Games in progress on the pool's chains (with their phase deadlines)
+    pub hosted_games: MapView<GameId, HostedGame>,
+    /// Games matched by this lobby so far (the index of the next game id)
+    pub games_matched: RegisterView<u64>,
+    /// Rules for the games this lobby matches
+    pub game_rules: RegisterView<GameRules>,
+    /// How closely matched players' ratings must be (the window widens while they wait)
+    pub matchmaking_params: RegisterView<MatchmakingParams>,
+    /// Wait times of the players seated so far
+    pub match_wait_stats: RegisterView<MatchWaitStats>,
+    /// Open rooms (private ones hold their password hash; query through `getRooms`)
+    #[graphql(skip)]
+    pub rooms: MapView<RoomId, RoomInfo>,
+    /// Id given to the next room
+    pub next_room_id: RegisterView<RoomId>,
+    /// Room each player is in (a player is in at most one room)
+    pub player_rooms: MapView<ChainId, RoomId>,
+    /// Game each player is seated at, until the game ends
+    pub seated_players: MapView<ChainId, GameId>,
+    /// Direct challenges waiting for an answer
+    pub challenges: MapView<u64, Challenge>,
+    /// Id given to the next challenge
+    pub next_challenge_id: RegisterView<u64>,
+    /// Cheat strikes and matchmaking bans per player
+    pub cheat_records: MapView<ChainId, CheatRecord>,
 
     // ============================================
     // GAME CHAIN STATE (instantiate_value = 2)
     // ============================================
-    /// Current active game on this chain
-    pub current_game: RegisterView<Option<LiarsDiceGame>>,
-    /// Players in current game (chain_id -> index)
-    pub game_players: MapView<ChainId, u8>,
-    /// Is this game chain available?
-    pub game_chain_available: RegisterView<bool>,
+    /// Games in progress on this chain
+    pub games: MapView<GameId, LiarsDiceGame>,
     /// Total games hosted on this chain
     pub games_hosted: RegisterView<u64>,
+    /// House seed for each game's current round (hidden from the service, but readable by anyone
+    /// syncing the chain - the round seed's unpredictability comes from the players' entropy shares)
+    #[graphql(skip)]
+    pub house_seeds: MapView<GameId, [u8; 32]>,
+    /// Private nonce for house seed entropy (incremented each round)
+    #[graphql(skip)]
+    pub house_rng_nonce: RegisterView<u64>,
+    /// Stakes locked by the players of each staked game, paid out when it ends
+    pub stake_escrows: MapView<GameId, StakeEscrow>,
+    /// Rematch offers for finished games, open until their deadline
+    pub rematch_offers: MapView<GameId, RematchOffer>,
+    /// Games this chain may host at once, as last pushed by the lobby (None: the default capacity)
+    pub pool_capacity: RegisterView<Option<u32>>,
+    /// Matchmaking bans pushed by the lobby, checked before a rematch seats anyone
+    pub banned_players: MapView<ChainId, Timestamp>,
+    /// User chains watching this chain's games (and when they started)
+    pub spectators: MapView<ChainId, Timestamp>,
+    /// Spectator feed events waiting out their delay, by game and sequence number
+    /// Hidden from the service only: anyone syncing the chain can read them (and the games themselves)
+    #[graphql(skip)]
+    pub spectator_backlog: MapView<(GameId, u64), DelayedSpectatorEvent>,
+    /// Games with held-back spectator events, and where each one's backlog starts and ends
+    #[graphql(skip)]
+    pub spectator_backlogs: MapView<GameId, SpectatorBacklog>,
+    /// Phase deadline of each game last reported to the lobby
+    pub reported_deadlines: MapView<GameId, Timestamp>,
+    /// Rejected messages whose origin did not match the claimed sender (audit log)
+    pub unauthorized_attempts: MapView<u64, UnauthorizedAttempt>,
+    /// Number of rejected messages (next audit log id)
+    pub unauthorized_attempt_count: RegisterView<u64>,
 
     // ============================================
     // USER CHAIN STATE (instantiate_value = 3)
@@ -64,12 +118,36 @@ pub struct LiarsDiceState {
     pub user_salt: RegisterView<Option<[u8; 32]>>,
     /// Current game chain user is connected to
     pub user_game_chain: RegisterView<Option<ChainId>>,
+    /// Current game on that chain
+    pub user_game_id: RegisterView<Option<GameId>>,
+    /// Stake the user locked for the current game
+    pub user_stake: RegisterView<Amount>,
+    /// Stakes of games the user left before they ended, settled when their result arrives
+    pub exited_stakes: MapView<GameId, Amount>,
     /// Current lobby chain
     pub lobby_chain: RegisterView<Option<ChainId>>,
     /// User's token balance (cached from bankroll)
     pub user_balance: RegisterView<Amount>,
+    /// Lobby queue size, as last announced by the lobby
+    pub queue_count: RegisterView<u32>,
     /// Last received game state (from event subscription)
     pub channel_game_state: RegisterView<Option<LiarsDiceGame>>,
+    /// Last action the game chain refused (cleared when the game ends)
+    pub last_rejection: RegisterView<Option<RejectedAction>>,
+    /// Game chain the user is watching
+    pub spectating: RegisterView<Option<ChainId>>,
+    /// What the user has seen of each game on the watched chain
+    pub spectated_games: MapView<GameId, SpectatorView>,
+    /// Rematch offer for the user's last game, if still open
+    pub user_rematch: RegisterView<Option<RematchOffer>>,
+    /// Room the user is in, as last sent by the lobby
+    pub user_room: RegisterView<Option<RoomInfo>>,
+    /// Last room or challenge action the lobby refused
+    pub last_room_rejection: RegisterView<Option<RejectedRoomAction>>,
+    /// Pending challenges this user sent or received, by challenge id
+    pub user_challenges: MapView<u64, Challenge>,
+    /// End of the user's matchmaking ban, as last sent by the lobby
+    pub matchmaking_banned_until: RegisterView<Option<Timestamp>>,
     /// Private nonce for RNG entropy (incremented each dice generation)
     #[graphql(skip)]
     pub rng_nonce: RegisterView<u64>,